@@ -0,0 +1,442 @@
+//! This module contains colors in spaces other then sRGB.
+//!
+//! A hue is in degrees [0, 360), a hue of an achromatic color is [f32::NAN].
+//!
+//! Conversions into `RGB<f32>` are not clamped,
+//! so a color which is out of sRGB gamut has components out of [0, 1] range.
+//!
+//! ```
+//! use tiny_gradient::{Hsl, RGB};
+//!
+//! let mut color = Hsl::from(RGB::new(255, 0, 0));
+//! color.l = 0.25;
+//!
+//! let darker: RGB = color.into();
+//!
+//! assert_eq!(darker, RGB::new(128, 0, 0));
+//! ```
+
+use crate::{
+    gradient::{srgb_apply_companding_color, srgb_inverse_color},
+    space, RGB,
+};
+
+/// Linear light RGB, components are in [0, 1] range.
+#[derive(Debug, Default, Clone, Copy, PartialEq, PartialOrd)]
+pub struct LinearRgb {
+    /// Red
+    pub r: f32,
+    /// Green
+    pub g: f32,
+    /// Blue
+    pub b: f32,
+}
+
+impl LinearRgb {
+    /// Creates a new [LinearRgb].
+    pub const fn new(r: f32, g: f32, b: f32) -> Self {
+        Self { r, g, b }
+    }
+
+    /// Decodes a gamma encoded sRGB component in [0, 1] range into linear light.
+    ///
+    /// ```
+    /// use tiny_gradient::LinearRgb;
+    ///
+    /// let c = LinearRgb::from_srgb_component(0.5);
+    ///
+    /// assert!((c - 0.214).abs() < 1e-3);
+    /// assert!((LinearRgb::to_srgb_component(c) - 0.5).abs() < 1e-6);
+    /// ```
+    pub fn from_srgb_component(c: f32) -> f32 {
+        srgb_inverse_color(c)
+    }
+
+    /// Encodes a linear light component back into gamma encoded sRGB.
+    pub fn to_srgb_component(c: f32) -> f32 {
+        srgb_apply_companding_color(c)
+    }
+}
+
+impl From<RGB> for LinearRgb {
+    fn from(c: RGB) -> Self {
+        Self::from(c.convert::<f32>())
+    }
+}
+
+impl From<RGB<f32>> for LinearRgb {
+    fn from(c: RGB<f32>) -> Self {
+        let [r, g, b] = space::srgb_to_linear(c);
+        Self { r, g, b }
+    }
+}
+
+impl From<LinearRgb> for RGB {
+    fn from(c: LinearRgb) -> Self {
+        RGB::<f32>::from(c).convert()
+    }
+}
+
+impl From<LinearRgb> for RGB<f32> {
+    fn from(LinearRgb { r, g, b }: LinearRgb) -> Self {
+        space::linear_to_srgb([r, g, b])
+    }
+}
+
+/// HSL, saturation and lightness are in [0, 1] range.
+#[derive(Debug, Default, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Hsl {
+    /// Hue
+    pub h: f32,
+    /// Saturation
+    pub s: f32,
+    /// Lightness
+    pub l: f32,
+}
+
+impl Hsl {
+    /// Creates a new [Hsl].
+    pub const fn new(h: f32, s: f32, l: f32) -> Self {
+        Self { h, s, l }
+    }
+}
+
+impl From<RGB> for Hsl {
+    fn from(c: RGB) -> Self {
+        Self::from(c.convert::<f32>())
+    }
+}
+
+impl From<RGB<f32>> for Hsl {
+    fn from(c: RGB<f32>) -> Self {
+        let [h, s, l] = space::srgb_to_hsl(c);
+        Self { h, s, l }
+    }
+}
+
+impl From<Hsl> for RGB {
+    fn from(c: Hsl) -> Self {
+        RGB::<f32>::from(c).convert()
+    }
+}
+
+impl From<Hsl> for RGB<f32> {
+    fn from(Hsl { h, s, l }: Hsl) -> Self {
+        space::hsl_to_srgb([h, s, l])
+    }
+}
+
+/// HSV, saturation and value are in [0, 1] range.
+#[derive(Debug, Default, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Hsv {
+    /// Hue
+    pub h: f32,
+    /// Saturation
+    pub s: f32,
+    /// Value
+    pub v: f32,
+}
+
+impl Hsv {
+    /// Creates a new [Hsv].
+    pub const fn new(h: f32, s: f32, v: f32) -> Self {
+        Self { h, s, v }
+    }
+}
+
+impl From<RGB> for Hsv {
+    fn from(c: RGB) -> Self {
+        Self::from(c.convert::<f32>())
+    }
+}
+
+impl From<RGB<f32>> for Hsv {
+    fn from(c: RGB<f32>) -> Self {
+        let [h, s, v] = space::srgb_to_hsv(c);
+        Self { h, s, v }
+    }
+}
+
+impl From<Hsv> for RGB {
+    fn from(c: Hsv) -> Self {
+        RGB::<f32>::from(c).convert()
+    }
+}
+
+impl From<Hsv> for RGB<f32> {
+    fn from(Hsv { h, s, v }: Hsv) -> Self {
+        space::hsv_to_srgb([h, s, v])
+    }
+}
+
+/// HWB, whiteness and blackness are in [0, 1] range.
+#[derive(Debug, Default, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Hwb {
+    /// Hue
+    pub h: f32,
+    /// Whiteness
+    pub w: f32,
+    /// Blackness
+    pub b: f32,
+}
+
+impl Hwb {
+    /// Creates a new [Hwb].
+    pub const fn new(h: f32, w: f32, b: f32) -> Self {
+        Self { h, w, b }
+    }
+}
+
+impl From<RGB> for Hwb {
+    fn from(c: RGB) -> Self {
+        Self::from(c.convert::<f32>())
+    }
+}
+
+impl From<RGB<f32>> for Hwb {
+    fn from(c: RGB<f32>) -> Self {
+        let [h, w, b] = space::srgb_to_hwb(c);
+        Self { h, w, b }
+    }
+}
+
+impl From<Hwb> for RGB {
+    fn from(c: Hwb) -> Self {
+        RGB::<f32>::from(c).convert()
+    }
+}
+
+impl From<Hwb> for RGB<f32> {
+    fn from(Hwb { h, w, b }: Hwb) -> Self {
+        space::hwb_to_srgb([h, w, b])
+    }
+}
+
+/// CIE Lab with D50 white point, lightness is in [0, 100] range.
+#[derive(Debug, Default, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Lab {
+    /// Lightness
+    pub l: f32,
+    /// Green-red axis
+    pub a: f32,
+    /// Blue-yellow axis
+    pub b: f32,
+}
+
+impl Lab {
+    /// Creates a new [Lab].
+    pub const fn new(l: f32, a: f32, b: f32) -> Self {
+        Self { l, a, b }
+    }
+}
+
+impl From<RGB> for Lab {
+    fn from(c: RGB) -> Self {
+        Self::from(c.convert::<f32>())
+    }
+}
+
+impl From<RGB<f32>> for Lab {
+    fn from(c: RGB<f32>) -> Self {
+        let [l, a, b] = space::srgb_to_lab(c);
+        Self { l, a, b }
+    }
+}
+
+impl From<Lab> for RGB {
+    fn from(c: Lab) -> Self {
+        RGB::<f32>::from(c).convert()
+    }
+}
+
+impl From<Lab> for RGB<f32> {
+    fn from(Lab { l, a, b }: Lab) -> Self {
+        space::lab_to_srgb([l, a, b])
+    }
+}
+
+/// CIE LCh, a cylindrical form of [Lab].
+#[derive(Debug, Default, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Lch {
+    /// Lightness
+    pub l: f32,
+    /// Chroma
+    pub c: f32,
+    /// Hue
+    pub h: f32,
+}
+
+impl Lch {
+    /// Creates a new [Lch].
+    pub const fn new(l: f32, c: f32, h: f32) -> Self {
+        Self { l, c, h }
+    }
+}
+
+impl From<RGB> for Lch {
+    fn from(c: RGB) -> Self {
+        Self::from(c.convert::<f32>())
+    }
+}
+
+impl From<RGB<f32>> for Lch {
+    fn from(c: RGB<f32>) -> Self {
+        let [l, c, h] = space::srgb_to_lch(c);
+        Self { l, c, h }
+    }
+}
+
+impl From<Lch> for RGB {
+    fn from(c: Lch) -> Self {
+        RGB::<f32>::from(c).convert()
+    }
+}
+
+impl From<Lch> for RGB<f32> {
+    fn from(Lch { l, c, h }: Lch) -> Self {
+        space::lch_to_srgb([l, c, h])
+    }
+}
+
+/// OKLab, lightness is in [0, 1] range.
+#[derive(Debug, Default, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Oklab {
+    /// Lightness
+    pub l: f32,
+    /// Green-red axis
+    pub a: f32,
+    /// Blue-yellow axis
+    pub b: f32,
+}
+
+impl Oklab {
+    /// Creates a new [Oklab].
+    pub const fn new(l: f32, a: f32, b: f32) -> Self {
+        Self { l, a, b }
+    }
+}
+
+impl From<RGB> for Oklab {
+    fn from(c: RGB) -> Self {
+        Self::from(c.convert::<f32>())
+    }
+}
+
+impl From<RGB<f32>> for Oklab {
+    fn from(c: RGB<f32>) -> Self {
+        let [l, a, b] = space::srgb_to_oklab(c);
+        Self { l, a, b }
+    }
+}
+
+impl From<Oklab> for RGB {
+    fn from(c: Oklab) -> Self {
+        RGB::<f32>::from(c).convert()
+    }
+}
+
+impl From<Oklab> for RGB<f32> {
+    fn from(Oklab { l, a, b }: Oklab) -> Self {
+        space::oklab_to_srgb([l, a, b])
+    }
+}
+
+/// OKLCH, a cylindrical form of [Oklab].
+#[derive(Debug, Default, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Oklch {
+    /// Lightness
+    pub l: f32,
+    /// Chroma
+    pub c: f32,
+    /// Hue
+    pub h: f32,
+}
+
+impl Oklch {
+    /// Creates a new [Oklch].
+    pub const fn new(l: f32, c: f32, h: f32) -> Self {
+        Self { l, c, h }
+    }
+}
+
+impl From<RGB> for Oklch {
+    fn from(c: RGB) -> Self {
+        Self::from(c.convert::<f32>())
+    }
+}
+
+impl From<RGB<f32>> for Oklch {
+    fn from(c: RGB<f32>) -> Self {
+        let [l, c, h] = space::srgb_to_oklch(c);
+        Self { l, c, h }
+    }
+}
+
+impl From<Oklch> for RGB {
+    fn from(c: Oklch) -> Self {
+        RGB::<f32>::from(c).convert()
+    }
+}
+
+impl From<Oklch> for RGB<f32> {
+    fn from(Oklch { l, c, h }: Oklch) -> Self {
+        space::oklch_to_srgb([l, c, h])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Hsl, Hsv, Hwb, Lab, Lch, LinearRgb, Oklab, Oklch};
+    use crate::RGB;
+
+    #[test]
+    fn round_trip_test() {
+        for c in [
+            RGB::new(0, 0, 0),
+            RGB::new(255, 255, 255),
+            RGB::new(128, 128, 128),
+            RGB::new(255, 0, 0),
+            RGB::new(18, 200, 97),
+            RGB::new(63, 81, 177),
+        ] {
+            assert_eq!(RGB::from(LinearRgb::from(c)), c);
+            assert_eq!(RGB::from(Hsl::from(c)), c);
+            assert_eq!(RGB::from(Hsv::from(c)), c);
+            assert_eq!(RGB::from(Hwb::from(c)), c);
+            assert_eq!(RGB::from(Lab::from(c)), c);
+            assert_eq!(RGB::from(Lch::from(c)), c);
+            assert_eq!(RGB::from(Oklab::from(c)), c);
+            assert_eq!(RGB::from(Oklch::from(c)), c);
+        }
+    }
+
+    #[test]
+    fn convert_test() {
+        let red = RGB::new(255, 0, 0);
+
+        assert_eq!(Hsl::from(red), Hsl::new(0.0, 1.0, 0.5));
+        assert_eq!(Hsv::from(red), Hsv::new(0.0, 1.0, 1.0));
+        assert_eq!(Hwb::from(red), Hwb::new(0.0, 0.0, 0.0));
+        assert_eq!(LinearRgb::from(red), LinearRgb::new(1.0, 0.0, 0.0));
+
+        let Oklch { l, c, h } = Oklch::from(red);
+        assert!((l - 0.628).abs() < 1e-3 && (c - 0.2577).abs() < 1e-3 && (h - 29.23).abs() < 0.1);
+
+        let Lab { l, a, b } = Lab::from(red);
+        assert!((l - 54.29).abs() < 0.1 && (a - 80.8).abs() < 0.1 && (b - 69.89).abs() < 0.1);
+
+        assert!(Hwb::from(RGB::new(128, 128, 128)).h.is_nan());
+        assert_eq!(
+            RGB::<u8>::from(Hwb::new(0.0, 0.6, 0.6)),
+            RGB::new(128, 128, 128)
+        );
+        assert_eq!(
+            RGB::from(Oklab::new(1.0, 0.0, 0.0)),
+            RGB::new(255, 255, 255)
+        );
+
+        // out of gamut
+        let c = RGB::<f32>::from(Oklch::new(0.7, 0.4, 150.0));
+        assert!(c.r < 0.0 && c.g > 0.0 && c.g < 1.0);
+    }
+}
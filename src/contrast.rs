@@ -0,0 +1,532 @@
+//! A module contains metrics of how readable and how different colors are.
+//!
+//! ```
+//! use tiny_gradient::{contrast, Gradient, RGB};
+//!
+//! let dark = RGB::new(0x1E, 0x1E, 0x1E);
+//!
+//! // WCAG asks for 3:1 for large text and 4.5:1 for a regular one
+//! assert!(contrast::min_contrast(&Gradient::Pastel, dark) >= 4.5);
+//! ```
+
+use core::f32::consts::PI;
+
+use libm::{atan2f, cosf, expf, fabsf, hypotf, powf, sinf, sqrtf};
+
+use crate::{
+    palette::{Palette, PaletteIter},
+    space::{luminance, oklch_in_gamut, srgb_to_lab, srgb_to_oklab, srgb_to_oklch},
+    RGB, RGBA,
+};
+
+/// A formula of a color difference.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum DeltaE {
+    /// A distance in CIE Lab.
+    Cie76,
+    /// CIE94 with weights for graphic arts.
+    Cie94,
+    /// CIEDE2000.
+    ///
+    /// It's a default option.
+    #[default]
+    Ciede2000,
+    /// A distance in OKLab, lightness is in [0, 1] range
+    /// so values are about 100 times less then the other ones.
+    Oklab,
+}
+
+impl RGB {
+    /// Returns WCAG 2 relative luminance in [0, 1] range.
+    pub fn luminance(self) -> f32 {
+        luminance(self.convert())
+    }
+
+    /// Returns WCAG 2 contrast ratio in [1, 21] range,
+    /// the order of colors doesn't matter.
+    ///
+    /// ```
+    /// use tiny_gradient::RGB;
+    ///
+    /// let black = RGB::new(0, 0, 0);
+    /// let white = RGB::new(255, 255, 255);
+    ///
+    /// assert_eq!(black.contrast(white).round(), 21.0);
+    /// ```
+    pub fn contrast(self, other: RGB) -> f32 {
+        let (l1, l2) = (self.luminance(), other.luminance());
+        (l1.max(l2) + 0.05) / (l1.min(l2) + 0.05)
+    }
+
+    /// Changes OKLCH lightness of the color, keeping its hue,
+    /// so it has at least a given WCAG 2 contrast ratio against a background.
+    ///
+    /// The color goes to the side of the background which has more room.
+    /// If the ratio can't be reached the closest color is returned,
+    /// a color which already meets the ratio is not changed.
+    ///
+    /// A ratio which isn't a finite number is ignored and the color is returned as it is.
+    ///
+    /// ```
+    /// use tiny_gradient::RGB;
+    ///
+    /// let white = RGB::new(255, 255, 255);
+    /// let yellow = RGB::new(255, 255, 0).ensure_contrast(white, 4.5);
+    ///
+    /// assert!(yellow.contrast(white) >= 4.5);
+    /// ```
+    pub fn ensure_contrast(self, background: RGB, ratio: f32) -> RGB {
+        if !ratio.is_finite() || self.contrast(background) >= ratio.clamp(1.0, 21.0) {
+            return self;
+        }
+
+        self.ensure_contrast_on(background, ratio, is_darker_side(background))
+    }
+
+    // Adjusts a color so it meets a ratio being on a given side of a background,
+    // darker or lighter one.
+    pub(crate) fn ensure_contrast_on(self, background: RGB, ratio: f32, darker: bool) -> RGB {
+        if !ratio.is_finite() {
+            return self;
+        }
+
+        let ratio = ratio.clamp(1.0, 21.0);
+        let y = background.luminance();
+        let meets = |c: RGB| {
+            let side = if darker {
+                c.luminance() <= y
+            } else {
+                c.luminance() >= y
+            };
+
+            side && c.contrast(background) >= ratio
+        };
+
+        if meets(self) {
+            return self;
+        }
+
+        let [l, chroma, h] = srgb_to_oklch(self.convert());
+        let color = |l: f32| oklch_in_gamut([l, chroma, h]).convert::<u8>();
+
+        let end = if darker { 0.0 } else { 1.0 };
+        if !meets(color(end)) {
+            return color(end);
+        }
+
+        // the closest lightness which meets the ratio
+        let (mut from, mut to) = (l, end);
+        for _ in 0..CONTRAST_STEPS {
+            let mid = (from + to) / 2.0;
+            if meets(color(mid)) {
+                to = mid;
+            } else {
+                from = mid;
+            }
+        }
+
+        color(to)
+    }
+
+    /// Returns APCA lightness contrast `Lc` of text over a background.
+    ///
+    /// It's positive for dark text on a light background
+    /// and negative for light text on a dark one,
+    /// about 60 is enough for a regular text.
+    ///
+    /// ```
+    /// use tiny_gradient::RGB;
+    ///
+    /// let black = RGB::new(0, 0, 0);
+    /// let white = RGB::new(255, 255, 255);
+    ///
+    /// assert_eq!(black.apca(white).round(), 106.0);
+    /// assert_eq!(white.apca(black).round(), -108.0);
+    /// ```
+    pub fn apca(self, background: RGB) -> f32 {
+        apca(apca_luminance(self), apca_luminance(background))
+    }
+
+    /// Returns a difference between colors.
+    ///
+    /// A value around 2.3 is a just noticeable difference for CIE formulas.
+    ///
+    /// ```
+    /// use tiny_gradient::{contrast::DeltaE, RGB};
+    ///
+    /// let red = RGB::new(255, 0, 0);
+    ///
+    /// assert_eq!(red.delta_e(red, DeltaE::Ciede2000), 0.0);
+    /// assert!(red.delta_e(RGB::new(250, 0, 0), DeltaE::Ciede2000) < 2.3);
+    /// ```
+    pub fn delta_e(self, other: RGB, formula: DeltaE) -> f32 {
+        let lab = |c: RGB| srgb_to_lab(c.convert());
+        match formula {
+            DeltaE::Cie76 => distance(lab(self), lab(other)),
+            DeltaE::Cie94 => cie94(lab(self), lab(other)),
+            DeltaE::Ciede2000 => ciede2000(lab(self), lab(other)),
+            DeltaE::Oklab => distance(
+                srgb_to_oklab(self.convert()),
+                srgb_to_oklab(other.convert()),
+            ),
+        }
+    }
+}
+
+/// Returns the lowest WCAG 2 contrast ratio of palette stops against a background.
+///
+/// Translucent stops are placed over the background.
+/// An empty palette gives [f32::INFINITY].
+///
+/// Only stops are checked, colors interpolated between them may have a lower contrast.
+pub fn min_contrast<P>(palette: &P, background: RGB) -> f32
+where
+    P: Palette + ?Sized,
+{
+    visible_colors(palette, background)
+        .map(|c| c.contrast(background))
+        .fold(f32::INFINITY, f32::min)
+}
+
+/// Returns the lowest absolute APCA `Lc` of palette stops over a background.
+///
+/// Translucent stops are placed over the background.
+/// An empty palette gives [f32::INFINITY].
+///
+/// Only stops are checked, see [min_contrast].
+pub fn min_apca<P>(palette: &P, background: RGB) -> f32
+where
+    P: Palette + ?Sized,
+{
+    visible_colors(palette, background)
+        .map(|c| fabsf(c.apca(background)))
+        .fold(f32::INFINITY, f32::min)
+}
+
+/// Returns the smallest difference between neighbor stops of a palette.
+///
+/// A palette with less then 2 stops gives [f32::INFINITY].
+pub fn min_delta_e<P>(palette: &P, formula: DeltaE) -> f32
+where
+    P: Palette + ?Sized,
+{
+    let colors = PaletteIter::new(palette).map(|stop| stop.color);
+    colors
+        .clone()
+        .zip(colors.skip(1))
+        .map(|(c1, c2)| c1.delta_e(c2, formula))
+        .fold(f32::INFINITY, f32::min)
+}
+
+fn visible_colors<P>(palette: &P, background: RGB) -> impl Iterator<Item = RGB> + '_
+where
+    P: Palette + ?Sized,
+{
+    PaletteIter::new(palette).map(move |stop| {
+        let c = stop.color;
+        RGBA::new(c.r, c.g, c.b, stop.alpha).composite(background)
+    })
+}
+
+// Checks whether there's more room for contrast below a background then above it.
+pub(crate) fn is_darker_side(background: RGB) -> bool {
+    let y = background.luminance();
+    (y + 0.05) / 0.05 >= 1.05 / (y + 0.05)
+}
+
+// A number of bisection steps of lightness,
+// it's more then enough for 8 bit colors.
+const CONTRAST_STEPS: usize = 16;
+
+// APCA 0.0.98G-4g constants.
+const APCA_COEFFICIENTS: [f32; 3] = [0.212_672_9, 0.715_152_2, 0.072_175];
+const APCA_BLACK_THRESHOLD: f32 = 0.022;
+const APCA_BLACK_CLAMP: f32 = 1.414;
+const APCA_SCALE: f32 = 1.14;
+const APCA_OFFSET: f32 = 0.027;
+const APCA_DELTA_MIN: f32 = 0.000_5;
+const APCA_CLIP: f32 = 0.1;
+
+// A screen luminance with a soft clamp of near black colors.
+fn apca_luminance(c: RGB) -> f32 {
+    let c = c.convert::<f32>();
+    let [r, g, b] = [c.r, c.g, c.b].map(|v| powf(v, 2.4));
+    let [kr, kg, kb] = APCA_COEFFICIENTS;
+    let y = kr * r + kg * g + kb * b;
+
+    if y < APCA_BLACK_THRESHOLD {
+        y + powf(APCA_BLACK_THRESHOLD - y, APCA_BLACK_CLAMP)
+    } else {
+        y
+    }
+}
+
+fn apca(text: f32, background: f32) -> f32 {
+    if fabsf(background - text) < APCA_DELTA_MIN {
+        return 0.0;
+    }
+
+    let lc = if background > text {
+        let c = (powf(background, 0.56) - powf(text, 0.57)) * APCA_SCALE;
+        if c < APCA_CLIP {
+            0.0
+        } else {
+            c - APCA_OFFSET
+        }
+    } else {
+        let c = (powf(background, 0.65) - powf(text, 0.62)) * APCA_SCALE;
+        if c > -APCA_CLIP {
+            0.0
+        } else {
+            c + APCA_OFFSET
+        }
+    };
+
+    lc * 100.0
+}
+
+fn distance([l1, a1, b1]: [f32; 3], [l2, a2, b2]: [f32; 3]) -> f32 {
+    let (dl, da, db) = (l1 - l2, a1 - a2, b1 - b2);
+    sqrtf(dl * dl + da * da + db * db)
+}
+
+fn cie94([l1, a1, b1]: [f32; 3], [l2, a2, b2]: [f32; 3]) -> f32 {
+    let (c1, c2) = (hypotf(a1, b1), hypotf(a2, b2));
+    let (dl, dc) = (l1 - l2, c1 - c2);
+    let (da, db) = (a1 - a2, b1 - b2);
+    let dh2 = (da * da + db * db - dc * dc).max(0.0);
+
+    let sc = 1.0 + 0.045 * c1;
+    let sh = 1.0 + 0.015 * c1;
+
+    sqrtf(dl * dl + (dc / sc) * (dc / sc) + dh2 / (sh * sh))
+}
+
+fn ciede2000([l1, a1, b1]: [f32; 3], [l2, a2, b2]: [f32; 3]) -> f32 {
+    let c_mean = (hypotf(a1, b1) + hypotf(a2, b2)) / 2.0;
+    let c7 = powf(c_mean, 7.0);
+    let g = 0.5 * (1.0 - sqrtf(c7 / (c7 + powf(25.0, 7.0))));
+
+    let (a1, a2) = (a1 * (1.0 + g), a2 * (1.0 + g));
+    let (c1, c2) = (hypotf(a1, b1), hypotf(a2, b2));
+    let hue = |a: f32, b: f32| {
+        if a == 0.0 && b == 0.0 {
+            0.0
+        } else {
+            let h = atan2f(b, a).to_degrees();
+            if h < 0.0 {
+                h + 360.0
+            } else {
+                h
+            }
+        }
+    };
+    let (h1, h2) = (hue(a1, b1), hue(a2, b2));
+
+    let dl = l2 - l1;
+    let dc = c2 - c1;
+    let dh = if c1 * c2 == 0.0 {
+        0.0
+    } else if fabsf(h2 - h1) <= 180.0 {
+        h2 - h1
+    } else if h2 - h1 > 180.0 {
+        h2 - h1 - 360.0
+    } else {
+        h2 - h1 + 360.0
+    };
+    let dh = 2.0 * sqrtf(c1 * c2) * sinf((dh / 2.0).to_radians());
+
+    let l_mean = (l1 + l2) / 2.0;
+    let c_mean = (c1 + c2) / 2.0;
+    let h_mean = if c1 * c2 == 0.0 {
+        h1 + h2
+    } else if fabsf(h1 - h2) <= 180.0 {
+        (h1 + h2) / 2.0
+    } else if h1 + h2 < 360.0 {
+        (h1 + h2 + 360.0) / 2.0
+    } else {
+        (h1 + h2 - 360.0) / 2.0
+    };
+
+    let deg = |d: f32| d * PI / 180.0;
+    let t = 1.0 - 0.17 * cosf(deg(h_mean - 30.0))
+        + 0.24 * cosf(deg(2.0 * h_mean))
+        + 0.32 * cosf(deg(3.0 * h_mean + 6.0))
+        - 0.20 * cosf(deg(4.0 * h_mean - 63.0));
+
+    let l50 = (l_mean - 50.0) * (l_mean - 50.0);
+    let sl = 1.0 + 0.015 * l50 / sqrtf(20.0 + l50);
+    let sc = 1.0 + 0.045 * c_mean;
+    let sh = 1.0 + 0.015 * c_mean * t;
+
+    let c7 = powf(c_mean, 7.0);
+    let rc = 2.0 * sqrtf(c7 / (c7 + powf(25.0, 7.0)));
+    let d_theta = 30.0 * expf(-((h_mean - 275.0) / 25.0) * ((h_mean - 275.0) / 25.0));
+    let rt = -sinf(deg(2.0 * d_theta)) * rc;
+
+    let (l, c, h) = (dl / sl, dc / sc, dh / sh);
+
+    sqrtf(l * l + c * c + h * h + rt * c * h)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ciede2000, min_apca, min_contrast, min_delta_e, DeltaE};
+    use crate::{stops::ColorStop, Gradient, RGB};
+
+    const BLACK: RGB = RGB::new(0, 0, 0);
+    const WHITE: RGB = RGB::new(255, 255, 255);
+
+    #[test]
+    fn contrast_test() {
+        assert_eq!(BLACK.contrast(BLACK), 1.0);
+        assert!((WHITE.contrast(BLACK) - 21.0).abs() < 1e-4);
+
+        let gray = RGB::new(118, 118, 118);
+        assert!((gray.contrast(WHITE) - 4.54).abs() < 0.01);
+
+        let gray = RGB::new(136, 136, 136);
+        assert!(
+            (gray.apca(WHITE) - 63.06).abs() < 0.1,
+            "{}",
+            gray.apca(WHITE)
+        );
+        assert_eq!(WHITE.apca(WHITE), 0.0);
+    }
+
+    #[test]
+    fn ensure_contrast_test() {
+        let backgrounds = [
+            BLACK,
+            WHITE,
+            RGB::new(0x1E, 0x1E, 0x1E),
+            RGB::new(128, 0, 255),
+        ];
+        for background in backgrounds {
+            for c in [
+                RGB::new(255, 255, 0),
+                RGB::new(0, 0, 128),
+                RGB::new(120, 200, 180),
+            ] {
+                for ratio in [3.0, 4.5, 7.0] {
+                    let got = c.ensure_contrast(background, ratio);
+                    let got = got.contrast(background);
+                    let best = BLACK.contrast(background).max(WHITE.contrast(background));
+                    assert!(got >= ratio.min(best - 0.1), "{:?} {:?}", c, background);
+                }
+            }
+        }
+
+        // a hue is kept
+        let c = RGB::new(255, 255, 0).ensure_contrast(WHITE, 4.5);
+        let [_, _, h] = crate::space::srgb_to_oklch(c.convert());
+        assert!((h - 110.0).abs() < 3.0, "{}", h);
+
+        let c = RGB::new(10, 20, 30);
+        assert_eq!(c.ensure_contrast(WHITE, 4.5), c);
+        assert_eq!(c.ensure_contrast(WHITE, 0.0), c);
+
+        // an invalid ratio is ignored
+        let c = RGB::new(0x20, 0x80, 0x20);
+        for ratio in [f32::NAN, f32::INFINITY, f32::NEG_INFINITY] {
+            assert_eq!(c.ensure_contrast(WHITE, ratio), c);
+            assert_eq!(c.ensure_contrast_on(WHITE, ratio, true), c);
+        }
+    }
+
+    #[test]
+    fn delta_e_test() {
+        // Sharma, Wu and Dalal test data
+        let pairs = [
+            ([50.0, 2.6772, -79.7751], [50.0, 0.0, -82.7485], 2.0425),
+            ([50.0, -1.0, 2.0], [50.0, 0.0, 0.0], 2.3669),
+            ([50.0, 2.5, 0.0], [73.0, 25.0, -18.0], 27.1492),
+            ([2.0776, 0.0795, -1.135], [0.9033, -0.0636, -0.5514], 0.9082),
+        ];
+        for (lab1, lab2, expected) in pairs {
+            let got = ciede2000(lab1, lab2);
+            assert!((got - expected).abs() < 1e-3, "{} != {}", got, expected);
+        }
+
+        let red = RGB::new(255, 0, 0);
+        for formula in [
+            DeltaE::Cie76,
+            DeltaE::Cie94,
+            DeltaE::Ciede2000,
+            DeltaE::Oklab,
+        ] {
+            assert_eq!(red.delta_e(red, formula), 0.0);
+            assert!(red.delta_e(WHITE, formula) > 0.0);
+        }
+
+        assert!((BLACK.delta_e(WHITE, DeltaE::Cie76) - 100.0).abs() < 1e-3);
+        assert!((BLACK.delta_e(WHITE, DeltaE::Oklab) - 1.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn palette_test() {
+        let palette = [WHITE, RGB::new(118, 118, 118)];
+        assert!((min_contrast(&palette, BLACK) - 4.63).abs() < 0.01);
+        assert!(min_apca(&palette, BLACK) > 30.0);
+        assert_eq!(min_delta_e(&[WHITE, WHITE, BLACK], DeltaE::Cie76), 0.0);
+        assert_eq!(min_delta_e(&[WHITE], DeltaE::Cie76), f32::INFINITY);
+
+        // a transparent stop is not visible
+        let palette = [ColorStop::from(WHITE).alpha(0)];
+        assert_eq!(min_contrast(&palette, BLACK), 1.0);
+        assert_eq!(min_contrast(&[] as &[RGB], BLACK), f32::INFINITY);
+    }
+
+    #[test]
+    fn theme_test() {
+        // default terminal backgrounds
+        const DARK: RGB = RGB::new(0x1E, 0x1E, 0x1E);
+        const LIGHT: RGB = WHITE;
+
+        // WCAG 2 minimum for large text
+        const READABLE: f32 = 3.0;
+
+        // whether a preset is readable on a dark and a light background,
+        // the ones which aren't are known to be unreadable and aren't checked
+        let themes = [
+            (Gradient::Atlast, true, false),
+            (Gradient::Cristal, true, false),
+            (Gradient::Teen, true, false),
+            (Gradient::Mind, false, false),
+            (Gradient::Morning, true, false),
+            (Gradient::Vice, true, false),
+            (Gradient::Passion, false, true),
+            (Gradient::Fruit, true, false),
+            (Gradient::Retro, false, false),
+            (Gradient::Summer, true, false),
+            (Gradient::Rainbow, false, false),
+            (Gradient::Pastel, true, false),
+            (Gradient::Monsoon, false, false),
+            (Gradient::Forest, false, true),
+            (Gradient::Instagram, false, false),
+        ];
+
+        for (gradient, dark, light) in themes {
+            for (background, readable) in [(DARK, dark), (LIGHT, light)] {
+                if !readable {
+                    continue;
+                }
+
+                // interpolated colors are checked as well as stops
+                let sampled = gradient
+                    .stops()
+                    .colors(64)
+                    .map(|c| c.contrast(background))
+                    .fold(f32::INFINITY, f32::min);
+                let stops = min_contrast(&gradient, background);
+
+                assert!(
+                    stops.min(sampled) >= READABLE,
+                    "{:?} on {:?}",
+                    gradient,
+                    background
+                );
+            }
+        }
+    }
+}
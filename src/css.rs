@@ -0,0 +1,679 @@
+//! A module contains a parser of CSS `linear-gradient()`.
+//!
+//! It's handy to pass a gradient as a single command line flag or an environment variable.
+//!
+//! ```
+//! use tiny_gradient::{css::LinearGradient, GradientStr};
+//!
+//! let gradient =
+//!     LinearGradient::parse("linear-gradient(90deg in oklch, #f00 0%, rgb(0 0 255) 40%, gold)")
+//!         .unwrap();
+//!
+//! let colored = "Hello World!"
+//!     .gradient(gradient)
+//!     .interpolation(gradient.interpolation());
+//!
+//! println!("{}", colored);
+//! ```
+
+use core::fmt::{self, Display};
+
+use libm::{atan2f, roundf};
+
+use crate::{
+    interpolation::{ColorSpace, HueInterpolation, Interpolation},
+    palette::{Palette, PaletteIter},
+    parse::{parse_color, parse_value, Value},
+    space::normalize_hue,
+    stops::{ColorStop, Positions},
+    Component, ParseRGBError, RGBErrorKind, RGB, RGBA,
+};
+
+/// A gradient parsed from a CSS `linear-gradient()` or a plain list of stops.
+///
+/// Accepted forms are
+///
+/// - `linear-gradient([<angle> | to <side> [<side>]] [in <space> [<hue> hue]], <stop>, ...)`
+/// - `<stop>, ...`
+///
+/// where a stop is a color followed by 0, 1 or 2 percentages.
+/// Colors are parsed the same way as [RGBA] does.
+///
+/// Text is colored along its lines,
+/// so a direction pointing to the left flips the gradient
+/// and a vertical one is ignored.
+///
+/// Stops are parsed once and kept inline,
+/// so there may be up to [LinearGradient::MAX_STOPS] of them.
+///
+/// ```
+/// use tiny_gradient::{css::LinearGradient, palette::Palette, RGB};
+///
+/// let gradient = LinearGradient::parse("#ff0000, #00ff00 50%, blue").unwrap();
+///
+/// assert_eq!(gradient.len(), 3);
+/// assert_eq!(gradient.stop(1).color, RGB::new(0, 255, 0));
+/// assert_eq!(gradient.stop(1).offset, Some(0.5));
+/// ```
+#[derive(Clone, Copy, PartialEq)]
+pub struct LinearGradient {
+    stops: [ColorStop; LinearGradient::MAX_STOPS],
+    len: usize,
+    direction: f32,
+    interpolation: Interpolation,
+}
+
+impl LinearGradient {
+    /// A maximum number of stops,
+    /// a color with 2 positions takes 2 of them.
+    pub const MAX_STOPS: usize = 32;
+
+    /// Parses a gradient.
+    ///
+    /// ```
+    /// use tiny_gradient::css::{GradientErrorKind, LinearGradient};
+    ///
+    /// let error = LinearGradient::parse("linear-gradient(red, bluish)").unwrap_err();
+    ///
+    /// assert_eq!(error.position(), 21);
+    /// assert!(matches!(error.kind(), GradientErrorKind::Color(_)));
+    /// ```
+    pub fn parse(s: &str) -> Result<Self, ParseGradientError> {
+        let trimmed = s.trim();
+        let mut list = match strip_function(trimmed) {
+            Some(args) => args
+                .strip_suffix(')')
+                .ok_or_else(|| ParseGradientError::new(GradientErrorKind::Parenthesis, s.len()))?,
+            None => trimmed,
+        };
+
+        let mut gradient = Self {
+            stops: [ColorStop::default(); Self::MAX_STOPS],
+            len: 0,
+            // the simple form goes along a line of text
+            direction: if list.len() == trimmed.len() {
+                90.0
+            } else {
+                180.0
+            },
+            interpolation: Interpolation::new(),
+        };
+
+        if let Some(line) = Args::new(list).next().filter(|arg| is_line(arg)) {
+            gradient.parse_line(line, s)?;
+            let end = (line.len() + 1).min(list.len());
+            list = &list[end..];
+        }
+
+        for arg in Args::new(list) {
+            let error = |kind, at| ParseGradientError::new(kind, position(s, at));
+
+            let (stop, positions, count) = parse_stop(arg).map_err(|(kind, at)| error(kind, at))?;
+
+            // a color with 2 positions makes 2 stops
+            let offsets = positions.map(Some);
+            let offsets = if count == 0 {
+                &[None][..]
+            } else {
+                &offsets[..count]
+            };
+            for &offset in offsets {
+                let slot = gradient
+                    .stops
+                    .get_mut(gradient.len)
+                    .ok_or_else(|| error(GradientErrorKind::TooMany, arg))?;
+
+                *slot = ColorStop { offset, ..stop };
+                gradient.len += 1;
+            }
+        }
+
+        if gradient.len == 0 {
+            let at = position(s, list);
+            return Err(ParseGradientError::new(GradientErrorKind::Empty, at));
+        }
+
+        if gradient.is_reversed() {
+            gradient.reverse();
+        }
+
+        Ok(gradient)
+    }
+
+    /// Returns an angle of the gradient line in degrees,
+    /// `0` goes to the top and `90` goes to the right.
+    ///
+    /// A default one is `180` for `linear-gradient()`, the same as in CSS,
+    /// and `90` for a plain list of stops.
+    pub fn direction(&self) -> f32 {
+        self.direction
+    }
+
+    /// Returns interpolation settings set by `in <space> [<hue> hue]`.
+    ///
+    /// If they're not set [Interpolation::new] is used.
+    pub fn interpolation(&self) -> Interpolation {
+        self.interpolation
+    }
+
+    // A direction has a part which goes to the left.
+    fn is_reversed(&self) -> bool {
+        self.direction > 180.0
+    }
+
+    // Flips stops resolving their offsets.
+    fn reverse(&mut self) {
+        let stops = &self.stops[..self.len];
+        let mut reversed = [ColorStop::default(); Self::MAX_STOPS];
+        for (i, stop) in Positions::new(stops.iter()).enumerate() {
+            reversed[self.len - 1 - i] = ColorStop {
+                offset: stop.offset.map(|offset| 1.0 - offset),
+                ..stop
+            };
+        }
+
+        self.stops = reversed;
+    }
+
+    fn parse_line(&mut self, line: &str, s: &str) -> Result<(), ParseGradientError> {
+        let error = |kind, at| Err(ParseGradientError::new(kind, position(s, at)));
+
+        // a direction and a color space may be set only once
+        let (mut has_direction, mut has_space) = (false, false);
+
+        let mut tokens = line.split_whitespace();
+        while let Some(token) = tokens.next() {
+            let is_space = token.eq_ignore_ascii_case("in");
+            if (is_space && has_space) || (!is_space && has_direction) {
+                return error(GradientErrorKind::Direction, token);
+            }
+
+            has_space |= is_space;
+            has_direction |= !is_space;
+
+            if token.eq_ignore_ascii_case("to") {
+                let (mut x, mut y) = (0.0, 0.0);
+                let mut sides = 0;
+                let mut rest = tokens.clone();
+                while let Some(side) = rest.next().and_then(side) {
+                    match side {
+                        (dx, 0.0) if x == 0.0 => x = dx,
+                        (0.0, dy) if y == 0.0 => y = dy,
+                        _ => break,
+                    }
+
+                    sides += 1;
+                    tokens = rest.clone();
+                }
+
+                if sides == 0 {
+                    return error(GradientErrorKind::Direction, token);
+                }
+
+                self.direction = normalize_hue(atan2f(x, y).to_degrees());
+            } else if token.eq_ignore_ascii_case("in") {
+                let space = match tokens.next() {
+                    Some(space) => space,
+                    None => return error(GradientErrorKind::Space, token),
+                };
+                let space = match color_space(space) {
+                    Some(space) => space,
+                    None => return error(GradientErrorKind::Space, space),
+                };
+                self.interpolation = self.interpolation.space(space);
+
+                let mut rest = tokens.clone();
+                let method = rest.next().unwrap_or("");
+                if let Some(hue) = hue_interpolation(method) {
+                    match rest.next() {
+                        Some(token) if token.eq_ignore_ascii_case("hue") => {
+                            self.interpolation = self.interpolation.hue(hue);
+                            tokens = rest;
+                        }
+                        _ => return error(GradientErrorKind::Hue, method),
+                    }
+                }
+            } else {
+                match parse_value(token, 0) {
+                    Ok(Value::Angle(angle)) => self.direction = normalize_hue(angle),
+                    _ => return error(GradientErrorKind::Direction, token),
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Palette for LinearGradient {
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    fn stop(&self, i: usize) -> ColorStop {
+        self.stops[..self.len][i]
+    }
+}
+
+impl fmt::Debug for LinearGradient {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LinearGradient")
+            .field("stops", &&self.stops[..self.len])
+            .field("direction", &self.direction)
+            .field("interpolation", &self.interpolation)
+            .finish()
+    }
+}
+
+impl IntoIterator for LinearGradient {
+    type Item = ColorStop;
+    type IntoIter = PaletteIter<Self>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        PaletteIter::new(self)
+    }
+}
+
+/// An error which can be returned when parsing a gradient.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseGradientError {
+    kind: GradientErrorKind,
+    pos: usize,
+}
+
+/// A cause of [ParseGradientError].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum GradientErrorKind {
+    /// There's no stops.
+    Empty,
+    /// `linear-gradient(` is not closed.
+    Parenthesis,
+    /// A direction is neither an angle nor `to` with sides.
+    Direction,
+    /// A color space is not one of
+    /// `srgb`, `srgb-linear`, `hsl`, `lab`, `lch`, `oklab` or `oklch`.
+    Space,
+    /// A hue interpolation is not followed by `hue`.
+    Hue,
+    /// A color of a stop is invalid.
+    Color(RGBErrorKind),
+    /// A position of a stop is not a percentage.
+    Position,
+    /// There are more then [LinearGradient::MAX_STOPS] stops.
+    TooMany,
+}
+
+impl ParseGradientError {
+    fn new(kind: GradientErrorKind, pos: usize) -> Self {
+        Self { kind, pos }
+    }
+
+    /// Outputs the detailed cause of parsing a gradient failing.
+    #[must_use]
+    pub fn kind(&self) -> &GradientErrorKind {
+        &self.kind
+    }
+
+    /// Returns a byte index in a parsed string where an error is found.
+    #[must_use]
+    pub fn position(&self) -> usize {
+        self.pos
+    }
+}
+
+impl Display for ParseGradientError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.kind {
+            GradientErrorKind::Empty => "gradient has no color stops".fmt(f)?,
+            GradientErrorKind::Parenthesis => "missing closing parenthesis".fmt(f)?,
+            GradientErrorKind::Direction => "invalid gradient direction".fmt(f)?,
+            GradientErrorKind::Space => "unsupported color space".fmt(f)?,
+            GradientErrorKind::Hue => "invalid hue interpolation".fmt(f)?,
+            GradientErrorKind::Color(kind) => {
+                "invalid color (".fmt(f)?;
+                ParseRGBError::new(kind.clone()).fmt(f)?;
+                ")".fmt(f)?;
+            }
+            GradientErrorKind::Position => "stop position must be a percentage".fmt(f)?,
+            GradientErrorKind::TooMany => "too many color stops".fmt(f)?,
+        }
+
+        " on position ".fmt(f)?;
+        self.pos.fmt(f)
+    }
+}
+
+/// A color formatted as a CSS `rgb()` function.
+///
+/// Alpha is written only if a color is translucent.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CssColor<C> {
+    color: C,
+}
+
+impl<C> CssColor<C> {
+    pub(crate) const fn new(color: C) -> Self {
+        Self { color }
+    }
+}
+
+impl Display for CssColor<RGB> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let RGB { r, g, b } = self.color;
+        f.write_fmt(format_args!("rgb({} {} {})", r, g, b))
+    }
+}
+
+impl Display for CssColor<RGBA> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let RGBA { r, g, b, a } = self.color;
+        if a == u8::MAX {
+            return CssColor::new(self.color.rgb()).fmt(f);
+        }
+
+        // 3 digits are enough to tell alpha values apart
+        let alpha = roundf(a.to_normalized() * 1000.0) / 1000.0;
+        f.write_fmt(format_args!("rgb({} {} {} / {})", r, g, b, alpha))
+    }
+}
+
+// Returns arguments of `linear-gradient(` with a closing parenthesis.
+fn strip_function(s: &str) -> Option<&str> {
+    const NAME: &str = "linear-gradient";
+
+    let name = s.get(..NAME.len())?;
+    if !name.eq_ignore_ascii_case(NAME) {
+        return None;
+    }
+
+    s[NAME.len()..].trim_start().strip_prefix('(')
+}
+
+// A first argument is a gradient line rather then a stop.
+fn is_line(arg: &str) -> bool {
+    let token = arg.split_whitespace().next().unwrap_or("");
+    token.eq_ignore_ascii_case("to")
+        || token.eq_ignore_ascii_case("in")
+        || matches!(parse_value(token, 0), Ok(Value::Angle(_)))
+}
+
+// Parses a color followed by up to 2 positions.
+fn parse_stop(arg: &str) -> Result<(ColorStop, [f32; 2], usize), (GradientErrorKind, &str)> {
+    let arg = arg.trim();
+
+    // a function may have spaces inside
+    let end = match arg.find('(') {
+        Some(open) if !arg[..open].contains(char::is_whitespace) => {
+            arg.find(')').map_or(arg.len(), |close| close + 1)
+        }
+        _ => arg.find(char::is_whitespace).unwrap_or(arg.len()),
+    };
+    let (color, rest) = arg.split_at(end);
+
+    let color = parse_color(color).map_err(|kind| (GradientErrorKind::Color(kind), color))?;
+    let stop = ColorStop::from(color.convert::<u8>());
+
+    let mut positions = [0.0; 2];
+    let mut len = 0;
+    for token in rest.split_whitespace() {
+        let offset = match parse_value(token, 0) {
+            Ok(Value::Percent(p)) => p / 100.0,
+            Ok(Value::Number(0.0)) => 0.0,
+            _ => return Err((GradientErrorKind::Position, token)),
+        };
+
+        match positions.get_mut(len) {
+            Some(position) => *position = offset,
+            None => return Err((GradientErrorKind::Position, token)),
+        }
+
+        len += 1;
+    }
+
+    Ok((stop, positions, len))
+}
+
+fn side(token: &str) -> Option<(f32, f32)> {
+    let is = |side: &str| token.eq_ignore_ascii_case(side);
+    if is("top") {
+        Some((0.0, 1.0))
+    } else if is("bottom") {
+        Some((0.0, -1.0))
+    } else if is("left") {
+        Some((-1.0, 0.0))
+    } else if is("right") {
+        Some((1.0, 0.0))
+    } else {
+        None
+    }
+}
+
+fn color_space(token: &str) -> Option<ColorSpace> {
+    let is = |space: &str| token.eq_ignore_ascii_case(space);
+    let space = if is("srgb") {
+        ColorSpace::Srgb
+    } else if is("srgb-linear") {
+        ColorSpace::LinearRgb
+    } else if is("hsl") {
+        ColorSpace::Hsl
+    } else if is("lab") {
+        ColorSpace::Lab
+    } else if is("lch") {
+        ColorSpace::Lch
+    } else if is("oklab") {
+        ColorSpace::Oklab
+    } else if is("oklch") {
+        ColorSpace::Oklch
+    } else {
+        return None;
+    };
+
+    Some(space)
+}
+
+fn hue_interpolation(token: &str) -> Option<HueInterpolation> {
+    let is = |hue: &str| token.eq_ignore_ascii_case(hue);
+    let hue = if is("shorter") {
+        HueInterpolation::Shorter
+    } else if is("longer") {
+        HueInterpolation::Longer
+    } else if is("increasing") {
+        HueInterpolation::Increasing
+    } else if is("decreasing") {
+        HueInterpolation::Decreasing
+    } else {
+        return None;
+    };
+
+    Some(hue)
+}
+
+// A byte index of a substring.
+fn position(s: &str, substring: &str) -> usize {
+    substring.as_ptr() as usize - s.as_ptr() as usize
+}
+
+// Splits arguments by commas which are not inside parentheses.
+#[derive(Debug, Clone)]
+struct Args<'a> {
+    rest: Option<&'a str>,
+}
+
+impl<'a> Args<'a> {
+    fn new(s: &'a str) -> Self {
+        let rest = if s.trim().is_empty() { None } else { Some(s) };
+        Self { rest }
+    }
+}
+
+impl<'a> Iterator for Args<'a> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let s = self.rest?;
+
+        let mut depth = 0_usize;
+        for (i, c) in s.char_indices() {
+            match c {
+                '(' => depth += 1,
+                ')' => depth = depth.saturating_sub(1),
+                ',' if depth == 0 => {
+                    self.rest = Some(&s[i + 1..]);
+                    return Some(&s[..i]);
+                }
+                _ => {}
+            }
+        }
+
+        self.rest = None;
+        Some(s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{GradientErrorKind, LinearGradient};
+    use crate::{
+        interpolation::{ColorSpace, HueInterpolation, Interpolation},
+        palette::Palette,
+        stops::ColorStop,
+        RGBErrorKind, RGB,
+    };
+
+    const RED: RGB = RGB::new(255, 0, 0);
+    const BLUE: RGB = RGB::new(0, 0, 255);
+    const GOLD: RGB = RGB::new(255, 215, 0);
+
+    #[test]
+    fn parse_test() {
+        let gradient = LinearGradient::parse(
+            "linear-gradient(90deg in oklch longer hue, #f00 0%, rgb(0 0 255) 40%, gold)",
+        )
+        .unwrap();
+
+        assert_eq!(gradient.direction(), 90.0);
+        assert_eq!(
+            gradient.interpolation(),
+            Interpolation::new()
+                .space(ColorSpace::Oklch)
+                .hue(HueInterpolation::Longer)
+        );
+        assert_eq!(
+            stops(gradient),
+            [
+                ColorStop::new(RED, 0.0),
+                ColorStop::new(BLUE, 0.4),
+                ColorStop::from(GOLD),
+            ]
+        );
+
+        let gradient = LinearGradient::parse(" #ff0000,#0000ff 20% 50% , #ffd70080").unwrap();
+        assert_eq!(gradient.direction(), 90.0);
+        assert_eq!(gradient.interpolation(), Interpolation::new());
+        assert_eq!(
+            stops(gradient),
+            [
+                ColorStop::from(RED),
+                ColorStop::new(BLUE, 0.2),
+                ColorStop::new(BLUE, 0.5),
+                ColorStop::from(GOLD).alpha(128),
+            ]
+        );
+
+        let gradient = LinearGradient::parse("LINEAR-GRADIENT (red, blue)").unwrap();
+        assert_eq!(gradient.direction(), 180.0);
+        assert_eq!(gradient.stop(0), ColorStop::from(RED));
+    }
+
+    #[test]
+    fn direction_test() {
+        let direction = |s: &str| LinearGradient::parse(s).unwrap().direction();
+
+        assert_eq!(direction("linear-gradient(to right, red, blue)"), 90.0);
+        assert_eq!(direction("linear-gradient(to top left, red, blue)"), 315.0);
+        assert_eq!(direction("linear-gradient(0.5turn, red, blue)"), 180.0);
+        assert_eq!(direction("linear-gradient(-90deg, red, blue)"), 270.0);
+
+        // a gradient which goes to the left is flipped
+        let gradient = LinearGradient::parse("linear-gradient(to left, red 20%, blue)").unwrap();
+        assert_eq!(
+            stops(gradient),
+            [ColorStop::new(BLUE, 0.0), ColorStop::new(RED, 0.8)]
+        );
+    }
+
+    #[test]
+    fn error_test() {
+        let error = |s: &str| {
+            let error = LinearGradient::parse(s).unwrap_err();
+            (error.kind().clone(), error.position())
+        };
+
+        assert_eq!(error(""), (GradientErrorKind::Empty, 0));
+        assert_eq!(
+            error("linear-gradient(to right)"),
+            (GradientErrorKind::Empty, 24)
+        );
+        assert_eq!(
+            error("linear-gradient(red, blue"),
+            (GradientErrorKind::Parenthesis, 25)
+        );
+        assert_eq!(
+            error("linear-gradient(to middle, red)"),
+            (GradientErrorKind::Direction, 16)
+        );
+        assert_eq!(
+            error("linear-gradient(in hwb, red)"),
+            (GradientErrorKind::Space, 19)
+        );
+        assert_eq!(
+            error("linear-gradient(in hsl longer, red)"),
+            (GradientErrorKind::Hue, 23)
+        );
+        assert_eq!(
+            error("red, rgb(1 2), blue"),
+            (
+                GradientErrorKind::Color(RGBErrorKind::Arguments { found: 2 }),
+                5
+            )
+        );
+        assert_eq!(error("red 10px, blue"), (GradientErrorKind::Position, 4));
+        assert_eq!(error("red 1% 2% 3%"), (GradientErrorKind::Position, 10));
+        assert_eq!(
+            error("red,, blue"),
+            (GradientErrorKind::Color(RGBErrorKind::Empty), 4)
+        );
+        assert_eq!(
+            error("linear-gradient(90deg 45deg, red, blue)"),
+            (GradientErrorKind::Direction, 22)
+        );
+        assert_eq!(
+            error("linear-gradient(to right 45deg, red, blue)"),
+            (GradientErrorKind::Direction, 25)
+        );
+        assert_eq!(
+            error("linear-gradient(in oklch in hsl, red, blue)"),
+            (GradientErrorKind::Direction, 25)
+        );
+
+        // one stop more then the limit
+        let mut list = [b' '; 5 * (LinearGradient::MAX_STOPS + 1)];
+        for chunk in list.chunks_mut(5) {
+            chunk[..4].copy_from_slice(b"red,");
+        }
+        list[5 * LinearGradient::MAX_STOPS + 3] = b' ';
+
+        let list = core::str::from_utf8(&list).unwrap();
+        assert_eq!(error(list), (GradientErrorKind::TooMany, 159));
+        assert_eq!(LinearGradient::parse(&list[..158]).unwrap().len(), 32);
+    }
+
+    fn stops<const N: usize>(gradient: LinearGradient) -> [ColorStop; N] {
+        assert_eq!(gradient.len(), N);
+        assert!(gradient
+            .into_iter()
+            .eq(core::array::from_fn::<_, N, _>(|i| gradient.stop(i))));
+        core::array::from_fn(|i| gradient.stop(i))
+    }
+}
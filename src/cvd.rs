@@ -0,0 +1,263 @@
+//! A module contains a simulation of color vision deficiencies.
+//!
+//! Colors are transformed with matrices of Machado, Oliveira and Fernandes (2009)
+//! with the highest severity, which is dichromacy.
+//!
+//! ```
+//! use tiny_gradient::{cvd, RGB};
+//!
+//! // a severity scale from green to red
+//! let severity = [RGB::new(0x2E, 0xCC, 0x40), RGB::new(0xFF, 0x41, 0x36)];
+//!
+//! for (deficiency, i) in cvd::indistinguishable(&severity, 10.0) {
+//!     println!("stops {} and {} look alike with {:?}", i, i + 1, deficiency);
+//! }
+//! ```
+
+use core::iter::FusedIterator;
+
+use crate::{
+    contrast::DeltaE,
+    palette::{impl_into_iterator, Palette},
+    space::{linear_to_srgb, multiply, srgb_to_linear},
+    stops::ColorStop,
+    RGB,
+};
+
+/// A kind of color blindness.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Deficiency {
+    /// No red cones.
+    Protanopia,
+    /// No green cones.
+    Deuteranopia,
+    /// No blue cones.
+    Tritanopia,
+}
+
+impl Deficiency {
+    /// All deficiencies.
+    pub const ALL: [Deficiency; 3] = [
+        Deficiency::Protanopia,
+        Deficiency::Deuteranopia,
+        Deficiency::Tritanopia,
+    ];
+
+    // A matrix which is applied to linear light RGB.
+    fn matrix(self) -> &'static [[f32; 3]; 3] {
+        match self {
+            Deficiency::Protanopia => &PROTANOPIA,
+            Deficiency::Deuteranopia => &DEUTERANOPIA,
+            Deficiency::Tritanopia => &TRITANOPIA,
+        }
+    }
+}
+
+impl RGB {
+    /// Returns a color the way it's seen with a given deficiency.
+    ///
+    /// ```
+    /// use tiny_gradient::{contrast::DeltaE, cvd::Deficiency, RGB};
+    ///
+    /// let green = RGB::new(0x4C, 0xAF, 0x50).simulate(Deficiency::Deuteranopia);
+    /// let red = RGB::new(0xF4, 0x43, 0x36).simulate(Deficiency::Deuteranopia);
+    ///
+    /// assert!(green.delta_e(red, DeltaE::Ciede2000) < 10.0);
+    /// ```
+    pub fn simulate(self, deficiency: Deficiency) -> RGB {
+        let c = multiply(deficiency.matrix(), srgb_to_linear(self.convert()));
+        linear_to_srgb(c.map(|v| v.clamp(0.0, 1.0))).convert()
+    }
+}
+
+/// Finds neighbor stops of a palette which can't be told apart
+/// with any of the deficiencies.
+///
+/// Stops differ if CIEDE2000 difference of simulated colors is at least `threshold`,
+/// about 10 makes sure colors are easy to tell apart.
+///
+/// It yields a deficiency and an index of the first stop of a pair.
+pub fn indistinguishable<P>(palette: &P, threshold: f32) -> Indistinguishable<'_, P>
+where
+    P: Palette + ?Sized,
+{
+    Indistinguishable {
+        palette,
+        threshold,
+        deficiency: 0,
+        i: 0,
+    }
+}
+
+/// Checks whether all neighbor stops of a palette can be told apart
+/// with each of the deficiencies.
+///
+/// See [indistinguishable].
+///
+/// ```
+/// use tiny_gradient::{cvd, RGB};
+///
+/// let palette = [RGB::new(0x00, 0x5A, 0xB5), RGB::new(0xDC, 0x32, 0x20)];
+///
+/// assert!(cvd::is_distinguishable(&palette, 10.0));
+/// ```
+pub fn is_distinguishable<P>(palette: &P, threshold: f32) -> bool
+where
+    P: Palette + ?Sized,
+{
+    indistinguishable(palette, threshold).next().is_none()
+}
+
+/// A palette seen with a deficiency.
+///
+/// ```
+/// use tiny_gradient::{cvd::Deficiency, palette::Palette, Gradient, GradientStr};
+///
+/// let preview = "Hello World!".gradient(Gradient::Rainbow.simulate(Deficiency::Deuteranopia));
+///
+/// println!("{}", preview);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Simulate<P> {
+    palette: P,
+    deficiency: Deficiency,
+}
+
+impl<P> Simulate<P> {
+    /// Creates a simulation of a palette.
+    pub const fn new(palette: P, deficiency: Deficiency) -> Self {
+        Self {
+            palette,
+            deficiency,
+        }
+    }
+}
+
+impl<P: Palette> Palette for Simulate<P> {
+    fn len(&self) -> usize {
+        self.palette.len()
+    }
+
+    fn stop(&self, i: usize) -> ColorStop {
+        let mut stop = self.palette.stop(i);
+        stop.color = stop.color.simulate(self.deficiency);
+        stop
+    }
+
+    fn offset(&self, i: usize) -> f32 {
+        self.palette.offset(i)
+    }
+}
+
+impl_into_iterator! {
+    [P: Palette] Simulate<P>,
+}
+
+/// An iterator of neighbor stops which look alike,
+/// it's created by [indistinguishable].
+#[derive(Debug, Clone)]
+pub struct Indistinguishable<'a, P: ?Sized> {
+    palette: &'a P,
+    threshold: f32,
+    deficiency: usize,
+    i: usize,
+}
+
+impl<P> Iterator for Indistinguishable<'_, P>
+where
+    P: Palette + ?Sized,
+{
+    type Item = (Deficiency, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(&deficiency) = Deficiency::ALL.get(self.deficiency) {
+            while self.i + 1 < self.palette.len() {
+                let i = self.i;
+                self.i += 1;
+
+                let c1 = self.palette.stop(i).color.simulate(deficiency);
+                let c2 = self.palette.stop(i + 1).color.simulate(deficiency);
+                if c1.delta_e(c2, DeltaE::Ciede2000) < self.threshold {
+                    return Some((deficiency, i));
+                }
+            }
+
+            self.deficiency += 1;
+            self.i = 0;
+        }
+
+        None
+    }
+}
+
+impl<P> FusedIterator for Indistinguishable<'_, P> where P: Palette + ?Sized {}
+
+const PROTANOPIA: [[f32; 3]; 3] = [
+    [0.152_286, 1.052_583, -0.204_868],
+    [0.114_503, 0.786_281, 0.099_216],
+    [-0.003_882, -0.048_116, 1.051_998],
+];
+
+const DEUTERANOPIA: [[f32; 3]; 3] = [
+    [0.367_322, 0.860_646, -0.227_968],
+    [0.280_085, 0.672_501, 0.047_413],
+    [-0.011_820, 0.042_940, 0.968_881],
+];
+
+const TRITANOPIA: [[f32; 3]; 3] = [
+    [1.255_528, -0.076_749, -0.178_779],
+    [-0.078_411, 0.930_809, 0.147_602],
+    [0.004_733, 0.691_367, 0.303_900],
+];
+
+#[cfg(test)]
+mod tests {
+    use super::{indistinguishable, is_distinguishable, Deficiency, RGB};
+    use crate::palette::Palette;
+
+    const GREEN: RGB = RGB::new(0x2E, 0xCC, 0x40);
+    const RED: RGB = RGB::new(0xFF, 0x41, 0x36);
+
+    #[test]
+    fn simulate_test() {
+        let grays = [
+            RGB::new(0, 0, 0),
+            RGB::new(128, 128, 128),
+            RGB::new(255, 255, 255),
+        ];
+        for deficiency in Deficiency::ALL {
+            for gray in grays {
+                let c = gray.simulate(deficiency);
+                let diff = |a: u8, b: u8| a.abs_diff(b) <= 1;
+                assert!(diff(c.r, gray.r) && diff(c.g, gray.g) && diff(c.b, gray.b));
+            }
+        }
+
+        let palette = [GREEN, RED].simulate(Deficiency::Deuteranopia);
+        assert_eq!(palette.len(), 2);
+        assert_eq!(
+            palette.stop(0).color,
+            GREEN.simulate(Deficiency::Deuteranopia)
+        );
+    }
+
+    #[test]
+    fn indistinguishable_test() {
+        // red looks darker with protanopia, so it's still different
+        let mut pairs = indistinguishable(&[GREEN, RED, RED], 10.0);
+        assert_eq!(pairs.next(), Some((Deficiency::Protanopia, 1)));
+        assert_eq!(pairs.next(), Some((Deficiency::Deuteranopia, 0)));
+        assert_eq!(pairs.next(), Some((Deficiency::Deuteranopia, 1)));
+        assert_eq!(pairs.next(), Some((Deficiency::Tritanopia, 1)));
+        assert_eq!(pairs.next(), None);
+        assert_eq!(pairs.next(), None);
+
+        let blue = RGB::new(0x00, 0x5A, 0xB5);
+        assert!(is_distinguishable(
+            &[blue, RED, RGB::new(255, 255, 255)],
+            10.0
+        ));
+        assert!(!is_distinguishable(&[blue, blue], 10.0));
+        assert!(is_distinguishable(&[RED], 10.0));
+    }
+}
@@ -1,45 +1,97 @@
 use core::fmt::{Display, Formatter, Result};
 
-use crate::{gradient::Gradient as GradientGenerator, RGB};
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+
+use crate::{
+    interpolation::{ColorSpace, Easing, Float, HueInterpolation, Interpolation, Spline},
+    palette::{Palette, PaletteIter},
+    quantize::{AnsiEscape, ColorDepth, Dithering, Quantizer, Target, TermColor},
+    stops::{Bands, ColorStop, Stops},
+    RGB,
+};
 
 /// Methods to colorize string with a gradient.
 pub trait GradientStr {
     /// This function takes a list of colors, which represent a gradient,
     /// and colorizes the string.
+    ///
+    /// Colors can be either [RGB] or [RGBA](crate::RGBA) which are evenly distributed,
+    /// or [ColorStop]s with explicit offsets.
     fn gradient<I>(&self, colors: I) -> GradientDisplay<'_, I>
     where
-        I: IntoIterator<Item = RGB> + Clone,
-        I::IntoIter: ExactSizeIterator + Clone;
+        I: IntoIterator + Clone,
+        I::IntoIter: Clone,
+        I::Item: Into<ColorStop>;
+
+    /// Colorizes the string with a [Palette],
+    /// e.g. a runtime list of colors or a trait object.
+    ///
+    /// ```
+    /// use tiny_gradient::{palette::Palette, GradientStr, Gradient, RGB};
+    ///
+    /// let colors: &[RGB] = &[RGB::new(0x01, 0x00, 0x00), RGB::new(0xDA, 0x00, 0xFF)];
+    /// println!("{}", "Hello World!".palette(colors));
+    ///
+    /// let palette: &dyn Palette = &Gradient::Forest;
+    /// println!("{}", "Hello World!".palette(palette));
+    /// ```
+    fn palette<P>(&self, palette: P) -> GradientDisplay<'_, PaletteIter<P>>
+    where
+        P: Palette + Clone,
+    {
+        self.gradient(PaletteIter::new(palette))
+    }
 }
 
+/// A gradient string representation which doesn't depend on a type of its palette.
+///
+/// ```
+/// use tiny_gradient::{palette::Palette, DynGradientDisplay, Gradient, GradientStr};
+///
+/// struct Theme<'a> {
+///     title: &'a dyn Palette,
+/// }
+///
+/// let theme = Theme { title: &Gradient::Vice };
+///
+/// let title: DynGradientDisplay<'_> = "Hello World!".palette(theme.title);
+///
+/// println!("{}", title);
+/// ```
+pub type DynGradientDisplay<'a> = GradientDisplay<'a, PaletteIter<&'a dyn Palette>>;
+
 impl GradientStr for str {
     fn gradient<I>(&self, colors: I) -> GradientDisplay<'_, I>
     where
-        I: IntoIterator<Item = RGB>,
-        I::IntoIter: ExactSizeIterator,
+        I: IntoIterator + Clone,
+        I::IntoIter: Clone,
+        I::Item: Into<ColorStop>,
     {
-        GradientDisplay::new(self, colors, ColorType::FOREGROUND)
+        GradientDisplay::new(self, colors, Target::Foreground)
     }
 }
 
 impl GradientStr for &str {
     fn gradient<I>(&self, colors: I) -> GradientDisplay<'_, I>
     where
-        I: IntoIterator<Item = RGB>,
-        I::IntoIter: ExactSizeIterator,
+        I: IntoIterator + Clone,
+        I::IntoIter: Clone,
+        I::Item: Into<ColorStop>,
     {
-        GradientDisplay::new(self, colors, ColorType::FOREGROUND)
+        GradientDisplay::new(self, colors, Target::Foreground)
     }
 }
 
-#[cfg(feature = "std")]
+#[cfg(feature = "alloc")]
 impl GradientStr for String {
     fn gradient<I>(&self, colors: I) -> GradientDisplay<'_, I>
     where
-        I: IntoIterator<Item = RGB>,
-        I::IntoIter: ExactSizeIterator,
+        I: IntoIterator + Clone,
+        I::IntoIter: Clone,
+        I::Item: Into<ColorStop>,
     {
-        GradientDisplay::new(self, colors, ColorType::FOREGROUND)
+        GradientDisplay::new(self, colors, Target::Foreground)
     }
 }
 
@@ -47,61 +99,199 @@ impl GradientStr for String {
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub struct GradientDisplay<'a, I> {
     text: &'a str,
-    colors: I,
-    color_type: ColorType,
+    stops: Stops<I>,
+    target: Target,
+    depth: ColorDepth,
+    dithering: Dithering,
+    contrast: Option<(RGB, Float)>,
 }
 
 impl<'a, I> GradientDisplay<'a, I> {
-    const fn new(text: &'a str, colors: I, color_type: ColorType) -> Self {
+    const fn new(text: &'a str, colors: I, target: Target) -> Self {
         Self {
             text,
-            colors,
-            color_type,
+            stops: Stops::new(colors),
+            target,
+            depth: ColorDepth::TrueColor,
+            dithering: Dithering::None,
+            contrast: None,
         }
     }
 
     /// Colorize background.
-    /// 
+    ///
     /// Default is foreground.
     pub const fn background(mut self) -> Self {
-        self.color_type = ColorType::BACKGROUND;
+        self.target = Target::Background;
         self
     }
 
     /// Colorize foreground.
-    /// 
+    ///
     /// It's a default option.
     pub const fn foreground(mut self) -> Self {
-        self.color_type = ColorType::FOREGROUND;
+        self.target = Target::Foreground;
+        self
+    }
+
+    /// Sets a number of colors a terminal supports.
+    ///
+    /// Default is [ColorDepth::TrueColor].
+    pub const fn depth(mut self, depth: ColorDepth) -> Self {
+        self.depth = depth;
+        self
+    }
+
+    /// Sets a dithering which is used when colors are reduced.
+    ///
+    /// Default is [Dithering::None].
+    pub const fn dithering(mut self, dithering: Dithering) -> Self {
+        self.dithering = dithering;
+        self
+    }
+
+    /// Adjusts lightness of each character color so it has at least
+    /// a given WCAG 2 contrast ratio against a terminal background.
+    ///
+    /// Colors are adjusted before they're reduced to [ColorDepth::Ansi256] or [ColorDepth::Ansi16],
+    /// so the ratio is guaranteed only with [ColorDepth::TrueColor].
+    ///
+    /// Each character color which doesn't meet the ratio takes 16 bisection steps of its lightness,
+    /// and each step reduces chroma in a loop until the color fits into sRGB gamut,
+    /// so printing becomes noticeably slower.
+    ///
+    /// See [RGB::ensure_contrast].
+    ///
+    /// ```
+    /// use tiny_gradient::{Gradient, GradientStr, RGB};
+    ///
+    /// let colored = "Hello World!"
+    ///     .gradient(Gradient::Pastel)
+    ///     .ensure_contrast(RGB::new(0xFF, 0xFF, 0xFF), 4.5);
+    ///
+    /// println!("{}", colored);
+    /// ```
+    pub const fn ensure_contrast(mut self, background: RGB, ratio: f32) -> Self {
+        self.contrast = Some((background, Float(ratio)));
+        self
+    }
+
+    /// Sets a color space in which colors are interpolated.
+    ///
+    /// Default is [ColorSpace::Corrected].
+    pub const fn space(mut self, space: ColorSpace) -> Self {
+        self.stops.interpolation.space = space;
+        self
+    }
+
+    /// Sets a direction of hue interpolation.
+    ///
+    /// Default is [HueInterpolation::Shorter].
+    pub const fn hue(mut self, hue: HueInterpolation) -> Self {
+        self.stops.interpolation.hue = hue;
+        self
+    }
+
+    /// Sets a curve which joins colors together.
+    ///
+    /// Default is [Spline::Linear].
+    pub const fn spline(mut self, spline: Spline) -> Self {
+        self.stops.interpolation.spline = spline;
+        self
+    }
+
+    /// Splits the gradient into `classes` equal bands of solid colors.
+    ///
+    /// `smoothness` is a fraction of a band width in [0, 1] range
+    /// over which neighbor bands are blended together.
+    ///
+    /// Zero `classes` turns it off.
+    pub const fn sharp(mut self, classes: usize, smoothness: f32) -> Self {
+        self.stops.bands = Bands::new(classes, smoothness);
+        self
+    }
+
+    /// Resamples the gradient so neighbor characters differ by the same perceptual distance.
+    pub const fn uniform(mut self) -> Self {
+        self.stops.uniform = true;
+        self
+    }
+
+    /// Sets a color which translucent colors are placed over,
+    /// usually it's a terminal background.
+    ///
+    /// By default alpha is ignored and colors are blended as if they were opaque.
+    ///
+    /// ```
+    /// use tiny_gradient::{GradientStr, RGB, RGBA};
+    ///
+    /// let faded = "Hello World!"
+    ///     .gradient([RGBA::new(0xFF, 0xFF, 0xFF, 0xFF), RGBA::new(0xFF, 0xFF, 0xFF, 0x00)])
+    ///     .backdrop(RGB::new(0x1E, 0x1E, 0x1E));
+    ///
+    /// println!("{}", faded);
+    /// ```
+    pub const fn backdrop(mut self, backdrop: RGB) -> Self {
+        self.stops.backdrop = Some(backdrop);
+        self
+    }
+
+    /// Sets all interpolation settings at once.
+    ///
+    /// An easing of [Interpolation] is applied to each pair of colors.
+    pub const fn interpolation(mut self, interpolation: Interpolation) -> Self {
+        self.stops.interpolation = interpolation;
+        self
+    }
+
+    /// Sets an easing of the whole gradient.
+    ///
+    /// Default is [Easing::Linear].
+    pub const fn easing(mut self, easing: Easing) -> Self {
+        self.stops.easing = easing;
+        self
+    }
+
+    /// Sets an easing applied to each pair of colors separately.
+    ///
+    /// Default is [Easing::Linear].
+    pub const fn segment_easing(mut self, easing: Easing) -> Self {
+        self.stops.interpolation.easing = easing;
         self
     }
 }
 
 impl<I> Display for GradientDisplay<'_, I>
 where
-    I: IntoIterator<Item = RGB> + Clone,
-    I::IntoIter: ExactSizeIterator + Clone,
+    I: IntoIterator + Clone,
+    I::IntoIter: Clone,
+    I::Item: Into<ColorStop>,
 {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result {
-        display_gradient(
-            self.text,
-            self.colors.clone().into_iter(),
-            self.color_type,
-            f,
-        )
+        let quantizer = Quantizer::new(self.depth, self.dithering);
+        let adjust = |c: RGB| match self.contrast {
+            Some((background, ratio)) => c.ensure_contrast(background, ratio.0),
+            None => c,
+        };
+
+        display_gradient(self.text, &self.stops, self.target, quantizer, adjust, f)
     }
 }
 
 fn display_gradient<I>(
     text: &str,
-    colors: I,
-    color_type: ColorType,
+    stops: &Stops<I>,
+    target: Target,
+    mut quantizer: Quantizer,
+    adjust: impl Fn(RGB) -> RGB,
     f: &mut Formatter<'_>,
 ) -> Result
 where
-    I: Iterator<Item = RGB> + Clone + ExactSizeIterator,
+    I: IntoIterator + Clone,
+    I::IntoIter: Clone,
+    I::Item: Into<ColorStop>,
 {
-    if colors.len() == 0 || text.is_empty() {
+    if text.is_empty() {
         return text.fmt(f);
     }
 
@@ -110,87 +300,71 @@ where
         return text.fmt(f);
     }
 
-    let mut gradient_chunk = line_width;
-    if colors.len() > 2 {
-        gradient_chunk /= colors.len() - 1;
+    // colors are cloned for each line so stops are measured once
+    if quantizer.is_exact() {
+        let colors = stops.colors(line_width);
+        let colors = || colors.clone();
+        let convert = |c, _, _| TermColor::Rgb(adjust(c));
+        return display_colors(text, colors, convert, target, f);
     }
 
-    let mut gradient = None;
-    let mut next_color = RGB::new(0, 0, 0);
-    let mut _colores = colors.clone();
-    let mut i = 1;
+    let colors = stops.colors_as::<f32>(line_width);
+    let colors = || colors.clone();
+    let quantize = |c: RGB<f32>, x, y| {
+        if x == 0 {
+            quantizer.reset();
+        }
+
+        // a color is kept precise unless it's changed
+        let rgb = c.convert();
+        let c = match adjust(rgb) {
+            adjusted if adjusted == rgb => c,
+            adjusted => adjusted.convert(),
+        };
+
+        quantizer.quantize(c, x, y)
+    };
+
+    display_colors(text, colors, quantize, target, f)
+}
+
+// Prints each line with a new set of colors.
+fn display_colors<C, T>(
+    text: &str,
+    mut colors: impl FnMut() -> C,
+    mut convert: impl FnMut(T, usize, usize) -> TermColor,
+    target: Target,
+    f: &mut Formatter<'_>,
+) -> Result
+where
+    C: Iterator<Item = T>,
+{
+    let mut gradient = colors();
+    let (mut x, mut y) = (0, 0);
     for c in text.chars() {
         if c == '\n' {
-            gradient = None;
-            i = 1;
+            gradient = colors();
+            x = 0;
+            y += 1;
             c.fmt(f)?;
             continue;
         }
 
-        // happens only on 1st iteration
-        if gradient.is_none() {
-            _colores = colors.clone();
-            let c1: RGB = _colores.next().unwrap();
-            let c2: RGB = _colores.next().unwrap_or(c1);
-            next_color = c2;
-
-            gradient = Some(GradientGenerator::new(c1, c2, gradient_chunk).into_iter());
+        match gradient.next() {
+            Some(color) => colorize_char(c, convert(color, x, y), target, f)?,
+            None => c.fmt(f)?,
         }
 
-        let color = match gradient.as_mut().unwrap().next() {
-            Some(color) => color,
-            None => {
-                i += 1;
-
-                let mut gradient_length = gradient_chunk;
-                let is_last_gradient = i + 1 == colors.len();
-                if is_last_gradient {
-                    gradient_length += line_width - gradient_chunk * i
-                };
-
-                let c1 = next_color;
-                let c2 = _colores.next().unwrap();
-                next_color = c2;
-
-                if gradient_length == 0 {
-                    gradient = Some(GradientGenerator::new(c1, c2, 0).into_iter());
-
-                    c2
-                } else {
-                    // we make +1 to be able to skip
-                    // 1st color because it was the last on prev iteration
-                    gradient =
-                        Some(GradientGenerator::new(c1, c2, gradient_length + 1).into_iter());
-                    let gradient = gradient.as_mut().unwrap();
-                    gradient.next().unwrap();
-                    gradient.next().unwrap()
-                }
-            }
-        };
-
-        colorize_char(c, color, color_type, f)?;
+        x += 1;
     }
 
     Ok(())
 }
 
-fn colorize_char(
-    c: char,
-    RGB { r, g, b }: RGB,
-    color_type: ColorType,
-    f: &mut Formatter<'_>,
-) -> Result {
+fn colorize_char(c: char, color: TermColor, target: Target, f: &mut Formatter<'_>) -> Result {
     f.write_fmt(format_args!(
-        "\x1b[{};2;{};{};{}m{}\x1b[0m",
-        color_type.0, r, g, b, c
+        "{}{}\x1b[0m",
+        AnsiEscape::new(color, target),
+        c
     ))
 }
-
-#[doc(hidden)]
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
-struct ColorType(usize);
-
-impl ColorType {
-    const BACKGROUND: ColorType = ColorType(48);
-    const FOREGROUND: ColorType = ColorType(38);
-}
@@ -1,23 +1,122 @@
 //! A module contains [Gradient] generator.
 
+use core::{iter::FusedIterator, marker::PhantomData};
+
 use libm::powf;
 
-use crate::rgb::RGB;
+use crate::{
+    interpolation::{ColorSpace, Easing, HueInterpolation, Interpolation},
+    rgb::{Component, RGB},
+};
 
 /// Gradient generator.
-/// 
+///
 /// It implements an [Iterator] interface.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct Gradient {
     from: RGB,
     to: RGB,
     steps: usize,
+    interpolation: Interpolation,
 }
 
 impl Gradient {
     /// Creates [Gradient] generator from one color to another in N steps.
     pub fn new(from: RGB, to: RGB, steps: usize) -> Self {
-        Self { from, to, steps }
+        Self {
+            from,
+            to,
+            steps,
+            interpolation: Interpolation::new(),
+        }
+    }
+
+    /// Sets a color space in which colors are interpolated.
+    ///
+    /// Default is [ColorSpace::Corrected].
+    pub const fn space(mut self, space: ColorSpace) -> Self {
+        self.interpolation.space = space;
+        self
+    }
+
+    /// Sets a direction of hue interpolation.
+    ///
+    /// Default is [HueInterpolation::Shorter].
+    pub const fn hue(mut self, hue: HueInterpolation) -> Self {
+        self.interpolation.hue = hue;
+        self
+    }
+
+    /// Sets all interpolation settings at once.
+    pub const fn interpolation(mut self, interpolation: Interpolation) -> Self {
+        self.interpolation = interpolation;
+        self
+    }
+
+    /// Sets an easing of the gradient.
+    ///
+    /// Default is [Easing::Linear].
+    pub const fn easing(mut self, easing: Easing) -> Self {
+        self.interpolation.easing = easing;
+        self
+    }
+
+    /// Returns a color at a given position in [0, 1] range.
+    ///
+    /// ```
+    /// use tiny_gradient::{gradient::Gradient, RGB};
+    ///
+    /// let gradient = Gradient::new(RGB::new(0, 0, 0), RGB::new(255, 255, 255), 10);
+    ///
+    /// assert_eq!(gradient.at(0.5), RGB::new(123, 123, 123));
+    /// ```
+    pub fn at(&self, t: f32) -> RGB {
+        self.at_as(t)
+    }
+
+    /// Returns a color at a given position in [0, 1] range
+    /// with a given component precision.
+    ///
+    /// ```
+    /// use tiny_gradient::{gradient::Gradient, RGB};
+    ///
+    /// let gradient = Gradient::new(RGB::new(0, 0, 0), RGB::new(255, 255, 255), 10);
+    ///
+    /// assert_eq!(gradient.at_as::<u16>(0.5), RGB::new(31716, 31716, 31716));
+    /// ```
+    pub fn at_as<T: Component>(&self, mut t: f32) -> RGB<T> {
+        if t.is_nan() {
+            t = 0.0;
+        }
+
+        self.color(t.clamp(0.0, 1.0))
+    }
+
+    /// Returns an iterator which yields colors with a given component precision.
+    ///
+    /// ```
+    /// use tiny_gradient::{gradient::Gradient, RGB};
+    ///
+    /// let gradient = Gradient::new(RGB::new(0, 0, 0), RGB::new(255, 255, 255), 3);
+    /// let mut colors = gradient.iter_as::<f32>();
+    ///
+    /// assert_eq!(colors.next(), Some(RGB::new(0.0, 0.0, 0.0)));
+    /// ```
+    pub fn iter_as<T: Component>(self) -> GradientIter<T> {
+        GradientIter {
+            gradient: self,
+            i: 0,
+            end: self.steps,
+            component: PhantomData,
+        }
+    }
+
+    fn color<T: Component>(&self, mix: f32) -> RGB<T> {
+        let from = normalize_rgb(self.from);
+        let to = normalize_rgb(self.to);
+
+        let c = self.interpolation.mix(from, to, mix);
+        self.interpolation.quantize(c)
     }
 }
 
@@ -26,49 +125,106 @@ impl IntoIterator for Gradient {
     type IntoIter = GradientIter;
 
     fn into_iter(self) -> Self::IntoIter {
-        GradientIter {
-            gradient: self,
-            i: 0,
-        }
+        self.iter_as()
     }
 }
 
 /// [Gradient] iterator which yields [RGB].
-pub struct GradientIter {
+///
+/// It can be iterated from both ends and any color is accessed in O(1).
+#[derive(Debug, Clone)]
+pub struct GradientIter<T = u8> {
     gradient: Gradient,
     i: usize,
+    end: usize,
+    component: PhantomData<T>,
 }
 
-impl Iterator for GradientIter {
-    type Item = RGB;
+impl<T: Component> GradientIter<T> {
+    fn color(&self, i: usize) -> RGB<T> {
+        let mut mix = i as f32 / (self.gradient.steps - 1) as f32;
+        if mix.is_nan() {
+            mix = 0.0;
+        }
+
+        self.gradient.color(mix)
+    }
+}
+
+impl<T: Component> Iterator for GradientIter<T> {
+    type Item = RGB<T>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.i == self.gradient.steps {
+        if self.i == self.end {
             return None;
         }
 
-        let mut mix = self.i as f32 / (self.gradient.steps - 1) as f32;
-        if mix.is_nan() {
-            mix = 0.0;
-        }
+        let color = self.color(self.i);
 
         self.i += 1;
 
-        let color = mix_color(self.gradient.from, self.gradient.to, mix);
-
         Some(color)
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.end - self.i;
+        (len, Some(len))
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.i = self.i.saturating_add(n).min(self.end);
+        self.next()
+    }
+
+    fn count(self) -> usize {
+        self.len()
+    }
+
+    fn last(mut self) -> Option<Self::Item> {
+        self.next_back()
+    }
+}
+
+impl<T: Component> DoubleEndedIterator for GradientIter<T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.i == self.end {
+            return None;
+        }
+
+        self.end -= 1;
+
+        Some(self.color(self.end))
+    }
+
+    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        self.end = self.end.saturating_sub(n).max(self.i);
+        self.next_back()
+    }
+}
+
+impl<T: Component> ExactSizeIterator for GradientIter<T> {}
+
+impl<T: Component> FusedIterator for GradientIter<T> {}
+
+//Empirically determined gamma used to compute a measure of brightness
+pub(crate) const BRIGHTNESS_GAMMA: f32 = 0.43;
+
+// Mixes colors with default settings.
+pub(crate) fn mix_color(c1: RGB, c2: RGB, mix: f32) -> RGB {
+    let interpolation = Interpolation::new();
+    let gamma = interpolation.brightness.map(|gamma| gamma.0);
+    let c = mix_corrected(c1.convert(), c2.convert(), mix, gamma);
+    interpolation.quantize(c)
 }
 
 // Mix [0..1]
 //      0   --> all c1
 //      0.5 --> equal mix of c1 and c2
 //      1   --> all c2
-fn mix_color(c1: RGB, c2: RGB, mix: f32) -> RGB {
-    //Convert color from 0..255 to 0..1
-    let c1 = normalize_rgb(c1);
-    let c2 = normalize_rgb(c2);
-
+//
+// Colors are normalized to 0..1,
+// a brightness is corrected if gamma is set.
+pub(crate) fn mix_corrected(c1: RGB<f32>, c2: RGB<f32>, mix: f32, gamma: Option<f32>) -> RGB<f32> {
     //Invert sRGB gamma compression
     let c1 = srgb_inverse_companding(c1);
     let c2 = srgb_inverse_companding(c2);
@@ -76,9 +232,8 @@ fn mix_color(c1: RGB, c2: RGB, mix: f32) -> RGB {
     let mut c = rgb_linear_interpolation(c1, c2, mix);
 
     //Apply adjustment factor to each rgb value based
-    if c.r + c.g + c.b != 0.0 {
-        //Compute a measure of brightness of the two colors using empirically determined gamma
-        let gamma = 0.43;
+    if let Some(gamma) = gamma.filter(|_| c.r + c.g + c.b != 0.0) {
+        //Compute a measure of brightness of the two colors
         let c1_bright = rgb_brightness(c1, gamma);
         let c2_bright = rgb_brightness(c2, gamma);
 
@@ -96,13 +251,11 @@ fn mix_color(c1: RGB, c2: RGB, mix: f32) -> RGB {
     }
 
     //Reapply sRGB gamma compression
-    let c = srgb_companding(c);
-
-    normalize_back_rgb(c)
+    srgb_companding(c)
 }
 
 //Inverse Red, Green, and Blue
-fn srgb_inverse_companding(c: RGB<f32>) -> RGB<f32> {
+pub(crate) fn srgb_inverse_companding(c: RGB<f32>) -> RGB<f32> {
     RGB {
         r: srgb_inverse_color(c.r),
         b: srgb_inverse_color(c.b),
@@ -110,7 +263,7 @@ fn srgb_inverse_companding(c: RGB<f32>) -> RGB<f32> {
     }
 }
 
-fn srgb_inverse_color(c: f32) -> f32 {
+pub(crate) fn srgb_inverse_color(c: f32) -> f32 {
     if c > 0.04045 {
         powf((c + 0.055) / 1.055, 2.4)
     } else {
@@ -118,7 +271,7 @@ fn srgb_inverse_color(c: f32) -> f32 {
     }
 }
 
-fn normalize_rgb(c: RGB) -> RGB<f32> {
+pub(crate) fn normalize_rgb(c: RGB) -> RGB<f32> {
     RGB {
         r: normalize_color(c.r),
         g: normalize_color(c.g),
@@ -126,19 +279,11 @@ fn normalize_rgb(c: RGB) -> RGB<f32> {
     }
 }
 
-fn normalize_back_rgb(c: RGB<f32>) -> RGB {
-    RGB {
-        r: (c.r * 255.9999) as u8,
-        g: (c.g * 255.9999) as u8,
-        b: (c.b * 255.9999) as u8,
-    }
-}
-
 fn normalize_color(c: u8) -> f32 {
     c as f32 / 255.0
 }
 
-fn rgb_linear_interpolation(c1: RGB<f32>, c2: RGB<f32>, mix: f32) -> RGB<f32> {
+pub(crate) fn rgb_linear_interpolation(c1: RGB<f32>, c2: RGB<f32>, mix: f32) -> RGB<f32> {
     RGB {
         r: linear_interpolation(c1.r, c2.r, mix),
         g: linear_interpolation(c1.g, c2.g, mix),
@@ -146,11 +291,11 @@ fn rgb_linear_interpolation(c1: RGB<f32>, c2: RGB<f32>, mix: f32) -> RGB<f32> {
     }
 }
 
-fn linear_interpolation(c1: f32, c2: f32, frac: f32) -> f32 {
+pub(crate) fn linear_interpolation(c1: f32, c2: f32, frac: f32) -> f32 {
     (c1 * (1.0 - frac)) + (c2 * frac)
 }
 
-fn srgb_companding(c: RGB<f32>) -> RGB<f32> {
+pub(crate) fn srgb_companding(c: RGB<f32>) -> RGB<f32> {
     RGB {
         r: srgb_apply_companding_color(c.r),
         g: srgb_apply_companding_color(c.g),
@@ -158,7 +303,7 @@ fn srgb_companding(c: RGB<f32>) -> RGB<f32> {
     }
 }
 
-fn srgb_apply_companding_color(c: f32) -> f32 {
+pub(crate) fn srgb_apply_companding_color(c: f32) -> f32 {
     if c > 0.0031308 {
         1.055 * powf(c, 1.0 / 2.4) - 0.055
     } else {
@@ -222,6 +367,53 @@ mod tests {
         );
     }
 
+    #[test]
+    fn gradient_iter_test() {
+        let gradient = Gradient::new(RGB::new(0, 0, 0), RGB::new(255, 255, 255), 3);
+
+        test_gradient(
+            gradient.into_iter().rev(),
+            &[
+                RGB::new(255, 255, 255),
+                RGB::new(123, 123, 123),
+                RGB::new(0, 0, 0),
+            ],
+        );
+
+        let mut iter = gradient.into_iter();
+        assert_eq!(iter.len(), 3);
+        assert_eq!(iter.nth(1), Some(RGB::new(123, 123, 123)));
+        assert_eq!(iter.len(), 1);
+        assert_eq!(iter.nth_back(0), Some(RGB::new(255, 255, 255)));
+        assert_eq!(iter.len(), 0);
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next_back(), None);
+
+        let mut iter = gradient.into_iter();
+        assert_eq!(iter.nth(5), None);
+        assert_eq!(iter.next_back(), None);
+
+        let mut iter = gradient.into_iter();
+        assert_eq!(iter.nth_back(2), Some(RGB::new(0, 0, 0)));
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    fn gradient_precision_test() {
+        let gradient = Gradient::new(RGB::new(0, 0, 0), RGB::new(255, 255, 255), 3);
+
+        let colors = [
+            RGB::new(0, 0, 0),
+            RGB::new(31716, 31716, 31716),
+            RGB::new(65535, 65535, 65535),
+        ];
+        assert!(gradient.iter_as::<u16>().eq(colors));
+
+        let c = gradient.at_as::<f32>(0.5);
+        assert!((c.r - 0.48395).abs() < 1e-4, "{:?}", c);
+        assert_eq!(gradient.at_as::<f32>(0.0), RGB::new(0.0, 0.0, 0.0));
+    }
+
     fn test_gradient(mut iter: impl Iterator<Item = RGB>, expected: &[RGB]) {
         for rgb in expected {
             let got = iter.next().unwrap();
@@ -1,4 +1,11 @@
-use crate::RGB;
+use core::iter::FusedIterator;
+
+use crate::{
+    palette::Palette,
+    random::{self, Rng},
+    stops::{ColorStop, Stops},
+    RGB,
+};
 
 /// A list of built-in gradient themes.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -35,12 +42,61 @@ pub enum Gradient {
     Instagram,
 }
 
-impl IntoIterator for &Gradient {
-    type Item = RGB;
-    type IntoIter = GradientIter<'static>;
+impl Gradient {
+    /// Returns a color of the gradient at a given position in [0, 1] range.
+    ///
+    /// ```
+    /// use tiny_gradient::{Gradient, RGB};
+    ///
+    /// assert_eq!(Gradient::Cristal.at(0.0), RGB::new(0xBD, 0xFF, 0xF3));
+    /// ```
+    pub fn at(self, t: f32) -> RGB {
+        self.stops()
+            .at(t)
+            .expect("a built-in gradient is never empty")
+    }
 
-    fn into_iter(self) -> Self::IntoIter {
-        let colors = match self {
+    /// Returns the gradient as a list of evenly distributed [Stops].
+    pub const fn stops(self) -> Stops<Self> {
+        Stops::new(self)
+    }
+
+    /// Creates a random palette from a seed.
+    ///
+    /// The same seed always gives the same colors.
+    /// They go from darker to lighter ones within a limited range of hues,
+    /// so a result is never muddy.
+    ///
+    /// ```
+    /// use tiny_gradient::{Gradient, GradientStr};
+    ///
+    /// let colors = Gradient::random(42);
+    ///
+    /// assert_eq!(colors, Gradient::random(42));
+    ///
+    /// println!("{}", "Hello World!".gradient(colors));
+    /// ```
+    pub fn random(seed: u64) -> [RGB; 3] {
+        random::palette(&mut Rng::new(seed))
+    }
+
+    /// Creates a random palette from a string, e.g. a user name or a hostname.
+    ///
+    /// The same string always gives the same colors on any platform.
+    ///
+    /// ```
+    /// use tiny_gradient::{Gradient, GradientStr};
+    ///
+    /// let user = "zhiburt";
+    ///
+    /// println!("{}", user.gradient(Gradient::random_from(user)));
+    /// ```
+    pub fn random_from(text: &str) -> [RGB; 3] {
+        Self::random(random::hash(text.as_bytes()))
+    }
+
+    fn colors(self) -> &'static [RGB] {
+        match self {
             Gradient::Atlast => GRADIENT_ATLAST.as_ref(),
             Gradient::Cristal => GRADIENT_CRISTAL.as_ref(),
             Gradient::Teen => GRADIENT_TEEN.as_ref(),
@@ -56,10 +112,35 @@ impl IntoIterator for &Gradient {
             Gradient::Monsoon => GRADIENT_MONSOON.as_ref(),
             Gradient::Forest => GRADIENT_FOREST.as_ref(),
             Gradient::Instagram => GRADIENT_INSTAGRAM.as_ref(),
-        };
+        }
+    }
+}
+
+impl IntoIterator for &Gradient {
+    type Item = RGB;
+    type IntoIter = GradientIter<'static>;
 
+    fn into_iter(self) -> Self::IntoIter {
         GradientIter {
-            colors: colors.iter(),
+            colors: self.colors().iter(),
+        }
+    }
+}
+
+impl Palette for Gradient {
+    fn len(&self) -> usize {
+        self.colors().len()
+    }
+
+    fn stop(&self, i: usize) -> ColorStop {
+        ColorStop::from(self.colors()[i])
+    }
+
+    fn offset(&self, i: usize) -> f32 {
+        // colors are evenly spaced
+        match self.len() {
+            1 => 0.0,
+            n => i as f32 / (n - 1) as f32,
         }
     }
 }
@@ -155,6 +236,15 @@ const GRADIENT_INSTAGRAM: [RGB; 3] = [
     RGB::new(0xfc, 0xb0, 0x45),
 ];
 
+/// [Gradient] iterator which yields its colors.
+///
+/// ```
+/// use tiny_gradient::{Gradient, GradientStr};
+///
+/// let colored = "Hello World!".gradient(Gradient::Forest.into_iter().rev());
+///
+/// println!("{}", colored);
+/// ```
 #[derive(Debug, Clone)]
 pub struct GradientIter<'a> {
     colors: core::slice::Iter<'a, RGB>,
@@ -166,6 +256,32 @@ impl Iterator for GradientIter<'_> {
     fn next(&mut self) -> Option<Self::Item> {
         self.colors.next().copied()
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.colors.size_hint()
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.colors.nth(n).copied()
+    }
+
+    fn count(self) -> usize {
+        self.colors.count()
+    }
+
+    fn last(self) -> Option<Self::Item> {
+        self.colors.last().copied()
+    }
+}
+
+impl DoubleEndedIterator for GradientIter<'_> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.colors.next_back().copied()
+    }
+
+    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        self.colors.nth_back(n).copied()
+    }
 }
 
 impl ExactSizeIterator for GradientIter<'_> {
@@ -173,3 +289,30 @@ impl ExactSizeIterator for GradientIter<'_> {
         self.colors.len()
     }
 }
+
+impl FusedIterator for GradientIter<'_> {}
+
+#[cfg(test)]
+mod tests {
+    use super::{Gradient, GRADIENT_RETRO, RGB};
+
+    #[test]
+    fn gradient_iter_test() {
+        let mut iter = Gradient::Retro.into_iter();
+        assert_eq!(iter.len(), GRADIENT_RETRO.len());
+        assert_eq!(iter.nth(2), Some(GRADIENT_RETRO[2]));
+        assert_eq!(iter.nth_back(2), Some(GRADIENT_RETRO[6]));
+        assert_eq!(iter.len(), 3);
+        assert_eq!(iter.next_back(), Some(GRADIENT_RETRO[5]));
+        assert_eq!(iter.last(), Some(GRADIENT_RETRO[4]));
+
+        let reversed = Gradient::Instagram.into_iter().rev();
+        assert!(reversed.eq(GRADIENT_INSTAGRAM_REVERSED));
+    }
+
+    const GRADIENT_INSTAGRAM_REVERSED: [RGB; 3] = [
+        RGB::new(0xfc, 0xb0, 0x45),
+        RGB::new(0xfd, 0x1d, 0x1d),
+        RGB::new(0x83, 0x3a, 0xb4),
+    ];
+}
@@ -0,0 +1,116 @@
+//! A module contains palettes built from a single base color.
+//!
+//! Hues are picked on HSL color wheel,
+//! each palette starts with the base color except [monochromatic].
+//!
+//! ```
+//! use tiny_gradient::{harmony, GradientStr, RGB};
+//!
+//! let brand = RGB::new(0x3F, 0x51, 0xB1);
+//!
+//! println!("{}", "Hello World!".gradient(harmony::triadic(brand)));
+//! ```
+
+use crate::{
+    gradient::normalize_rgb,
+    space::{hsl_to_srgb, normalize_hue, srgb_to_hsl},
+    RGB,
+};
+
+/// Builds `N` shades of a color going from dark to light.
+///
+/// ```
+/// use tiny_gradient::{harmony, RGB};
+///
+/// let shades: [RGB; 3] = harmony::monochromatic(RGB::new(255, 0, 0));
+///
+/// assert_eq!(shades, [RGB::new(128, 0, 0), RGB::new(255, 0, 0), RGB::new(255, 128, 128)]);
+/// ```
+pub fn monochromatic<const N: usize>(base: RGB) -> [RGB; N] {
+    let [h, s, _] = srgb_to_hsl(normalize_rgb(base));
+
+    core::array::from_fn(|i| {
+        let l = (i + 1) as f32 / (N + 1) as f32;
+        hsl_to_srgb([h, s, l]).convert()
+    })
+}
+
+/// Builds a color with its neighbors on a color wheel, 30° apart.
+pub fn analogous(base: RGB) -> [RGB; 3] {
+    rotate(base, [0.0, 30.0, -30.0])
+}
+
+/// Builds a color with an opposite one.
+///
+/// ```
+/// use tiny_gradient::{harmony, RGB};
+///
+/// let colors = harmony::complementary(RGB::new(255, 0, 0));
+///
+/// assert_eq!(colors, [RGB::new(255, 0, 0), RGB::new(0, 255, 255)]);
+/// ```
+pub fn complementary(base: RGB) -> [RGB; 2] {
+    rotate(base, [0.0, 180.0])
+}
+
+/// Builds a color with 2 neighbors of an opposite one.
+pub fn split_complementary(base: RGB) -> [RGB; 3] {
+    rotate(base, [0.0, 150.0, 210.0])
+}
+
+/// Builds 3 colors evenly spaced on a color wheel.
+pub fn triadic(base: RGB) -> [RGB; 3] {
+    rotate(base, [0.0, 120.0, 240.0])
+}
+
+/// Builds 2 pairs of complementary colors which form a rectangle on a color wheel.
+pub fn tetradic(base: RGB) -> [RGB; 4] {
+    rotate(base, [0.0, 60.0, 180.0, 240.0])
+}
+
+fn rotate<const N: usize>(base: RGB, angles: [f32; N]) -> [RGB; N] {
+    let [h, s, l] = srgb_to_hsl(normalize_rgb(base));
+
+    angles.map(|angle| match angle {
+        0.0 => base,
+        angle => hsl_to_srgb([normalize_hue(h + angle), s, l]).convert(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{analogous, monochromatic, split_complementary, tetradic, triadic, RGB};
+
+    const BASE: RGB = RGB::new(255, 0, 0);
+
+    #[test]
+    fn harmony_test() {
+        assert_eq!(
+            analogous(BASE),
+            [BASE, RGB::new(255, 128, 0), RGB::new(255, 0, 128)]
+        );
+        assert_eq!(
+            split_complementary(BASE),
+            [BASE, RGB::new(0, 255, 128), RGB::new(0, 128, 255)]
+        );
+        assert_eq!(
+            triadic(BASE),
+            [BASE, RGB::new(0, 255, 0), RGB::new(0, 0, 255)]
+        );
+        assert_eq!(
+            tetradic(BASE),
+            [
+                BASE,
+                RGB::new(255, 255, 0),
+                RGB::new(0, 255, 255),
+                RGB::new(0, 0, 255)
+            ]
+        );
+
+        // a gray has no hue
+        let gray = RGB::new(128, 128, 128);
+        assert_eq!(triadic(gray), [gray; 3]);
+        assert_eq!(monochromatic::<1>(gray), [gray]);
+        assert_eq!(monochromatic::<0>(gray), []);
+    }
+}
@@ -0,0 +1,999 @@
+//! A module contains settings of how colors are blended together.
+
+use core::{
+    cmp::Ordering,
+    f32::consts::PI,
+    fmt::{self, Debug, Formatter},
+    hash::{Hash, Hasher},
+};
+
+use libm::{cosf, exp2f, powf, sinf};
+
+use crate::{
+    gradient::{linear_interpolation, mix_corrected, rgb_linear_interpolation, BRIGHTNESS_GAMMA},
+    space::{self, normalize_hue},
+    Component, RGB, RGBA,
+};
+
+/// A color space in which colors are interpolated.
+///
+/// ```
+/// use tiny_gradient::{gradient::Gradient, interpolation::ColorSpace, RGB};
+///
+/// let gradient = Gradient::new(RGB::new(0xFF, 0x00, 0x00), RGB::new(0x00, 0x00, 0xFF), 10)
+///     .space(ColorSpace::Oklch);
+///
+/// for color in gradient {
+///     println!("{:?}", color);
+/// }
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ColorSpace {
+    /// Gamma encoded sRGB.
+    Srgb,
+    /// Linear light RGB.
+    LinearRgb,
+    /// Linear light RGB with a brightness correction.
+    ///
+    /// It's a default option.
+    #[default]
+    Corrected,
+    /// HSL
+    Hsl,
+    /// HSV
+    Hsv,
+    /// CIE Lab
+    Lab,
+    /// CIE LCh
+    Lch,
+    /// OKLab
+    Oklab,
+    /// OKLCH
+    Oklch,
+}
+
+/// A direction in which a hue is interpolated in cylindrical color spaces.
+///
+/// It's taken into account only by [ColorSpace::Hsl], [ColorSpace::Hsv],
+/// [ColorSpace::Lch] and [ColorSpace::Oklch].
+/// In case of multiple colors it's applied to each pair of them.
+///
+/// ```
+/// use tiny_gradient::{
+///     gradient::Gradient,
+///     interpolation::{ColorSpace, HueInterpolation},
+///     RGB,
+/// };
+///
+/// let red = RGB::new(0xFF, 0x00, 0x00);
+///
+/// let rainbow = Gradient::new(red, red, 10)
+///     .space(ColorSpace::Hsl)
+///     .hue(HueInterpolation::Longer);
+///
+/// for color in rainbow {
+///     println!("{:?}", color);
+/// }
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum HueInterpolation {
+    /// Takes the shorter arc.
+    ///
+    /// It's a default option.
+    #[default]
+    Shorter,
+    /// Takes the longer arc.
+    Longer,
+    /// Goes clockwise, so a hue increases.
+    Increasing,
+    /// Goes counterclockwise, so a hue decreases.
+    Decreasing,
+}
+
+/// A curve which defines how fast a gradient progresses.
+///
+/// ```
+/// use tiny_gradient::{interpolation::Easing, Gradient, GradientStr};
+///
+/// let text = "Hello World!";
+///
+/// let colored = text.gradient(Gradient::Forest).easing(Easing::ExpoIn);
+///
+/// println!("{}", colored);
+/// ```
+#[derive(Debug, Default, Clone, Copy)]
+pub enum Easing {
+    /// A constant speed.
+    ///
+    /// It's a default option.
+    #[default]
+    Linear,
+    /// CSS `ease`, the same as `cubic-bezier(0.25, 0.1, 0.25, 1)`.
+    Ease,
+    /// CSS `ease-in`, the same as `cubic-bezier(0.42, 0, 1, 1)`.
+    EaseIn,
+    /// CSS `ease-out`, the same as `cubic-bezier(0, 0, 0.58, 1)`.
+    EaseOut,
+    /// CSS `ease-in-out`, the same as `cubic-bezier(0.42, 0, 0.58, 1)`.
+    EaseInOut,
+    /// `t^p`
+    PowerIn(f32),
+    /// `1 - (1 - t)^p`
+    PowerOut(f32),
+    /// [Easing::PowerIn] on the first half and [Easing::PowerOut] on the second one.
+    PowerInOut(f32),
+    /// Sine curve which starts slowly.
+    SineIn,
+    /// Sine curve which ends slowly.
+    SineOut,
+    /// Sine curve which starts and ends slowly.
+    SineInOut,
+    /// Exponential curve which starts slowly.
+    ExpoIn,
+    /// Exponential curve which ends slowly.
+    ExpoOut,
+    /// Exponential curve which starts and ends slowly.
+    ExpoInOut,
+    /// Hermite polynomial `t^2 * (3 - 2t)`.
+    Smoothstep,
+    /// A custom CSS `cubic-bezier(x1, y1, x2, y2)`.
+    ///
+    /// `x1` and `x2` are clamped to [0, 1] range.
+    CubicBezier(f32, f32, f32, f32),
+}
+
+impl Easing {
+    /// Maps a position in [0, 1] range to an eased one.
+    ///
+    /// The result is clamped to [0, 1] range.
+    ///
+    /// ```
+    /// use tiny_gradient::interpolation::Easing;
+    ///
+    /// assert_eq!(Easing::PowerIn(2.0).apply(0.5), 0.25);
+    /// ```
+    pub fn apply(self, t: f32) -> f32 {
+        if t.is_nan() {
+            return 0.0;
+        }
+
+        let t = t.clamp(0.0, 1.0);
+        let eased = match self {
+            Easing::Linear => t,
+            Easing::Ease => cubic_bezier(0.25, 0.1, 0.25, 1.0, t),
+            Easing::EaseIn => cubic_bezier(0.42, 0.0, 1.0, 1.0, t),
+            Easing::EaseOut => cubic_bezier(0.0, 0.0, 0.58, 1.0, t),
+            Easing::EaseInOut => cubic_bezier(0.42, 0.0, 0.58, 1.0, t),
+            Easing::PowerIn(p) => powf(t, p),
+            Easing::PowerOut(p) => 1.0 - powf(1.0 - t, p),
+            Easing::PowerInOut(p) if t < 0.5 => powf(2.0 * t, p) / 2.0,
+            Easing::PowerInOut(p) => 1.0 - powf(2.0 - 2.0 * t, p) / 2.0,
+            Easing::SineIn => 1.0 - cosf(t * PI / 2.0),
+            Easing::SineOut => sinf(t * PI / 2.0),
+            Easing::SineInOut => (1.0 - cosf(t * PI)) / 2.0,
+            Easing::ExpoIn if t == 0.0 => 0.0,
+            Easing::ExpoIn => exp2f(10.0 * t - 10.0),
+            Easing::ExpoOut if t == 1.0 => 1.0,
+            Easing::ExpoOut => 1.0 - exp2f(-10.0 * t),
+            Easing::ExpoInOut if t == 0.0 || t == 1.0 => t,
+            Easing::ExpoInOut if t < 0.5 => exp2f(20.0 * t - 10.0) / 2.0,
+            Easing::ExpoInOut => (2.0 - exp2f(10.0 - 20.0 * t)) / 2.0,
+            Easing::Smoothstep => t * t * (3.0 - 2.0 * t),
+            Easing::CubicBezier(x1, y1, x2, y2) => cubic_bezier(x1, y1, x2, y2, t),
+        };
+
+        if eased.is_nan() {
+            return 0.0;
+        }
+
+        eased.clamp(0.0, 1.0)
+    }
+
+    // A variant index and parameters,
+    // so easings are compared the way a derive would do it.
+    fn key(&self) -> (u8, [Float; 4]) {
+        let (i, p) = match *self {
+            Easing::Linear => (0, [0.0; 4]),
+            Easing::Ease => (1, [0.0; 4]),
+            Easing::EaseIn => (2, [0.0; 4]),
+            Easing::EaseOut => (3, [0.0; 4]),
+            Easing::EaseInOut => (4, [0.0; 4]),
+            Easing::PowerIn(p) => (5, [p, 0.0, 0.0, 0.0]),
+            Easing::PowerOut(p) => (6, [p, 0.0, 0.0, 0.0]),
+            Easing::PowerInOut(p) => (7, [p, 0.0, 0.0, 0.0]),
+            Easing::SineIn => (8, [0.0; 4]),
+            Easing::SineOut => (9, [0.0; 4]),
+            Easing::SineInOut => (10, [0.0; 4]),
+            Easing::ExpoIn => (11, [0.0; 4]),
+            Easing::ExpoOut => (12, [0.0; 4]),
+            Easing::ExpoInOut => (13, [0.0; 4]),
+            Easing::Smoothstep => (14, [0.0; 4]),
+            Easing::CubicBezier(x1, y1, x2, y2) => (15, [x1, y1, x2, y2]),
+        };
+
+        (i, p.map(Float))
+    }
+}
+
+impl PartialEq for Easing {
+    fn eq(&self, other: &Self) -> bool {
+        self.key() == other.key()
+    }
+}
+
+impl Eq for Easing {}
+
+impl PartialOrd for Easing {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Easing {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.key().cmp(&other.key())
+    }
+}
+
+impl Hash for Easing {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.key().hash(state);
+    }
+}
+
+// A float which is compared by its bits,
+// so settings which hold one are `Eq`, `Ord` and `Hash`.
+#[derive(Clone, Copy)]
+pub(crate) struct Float(pub(crate) f32);
+
+impl PartialEq for Float {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Float {}
+
+impl PartialOrd for Float {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Float {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.total_cmp(&other.0)
+    }
+}
+
+impl Hash for Float {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.to_bits().hash(state);
+    }
+}
+
+impl Debug for Float {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+/// A curve which joins colors of a gradient together.
+///
+/// Splines are built through all stops in a chosen [ColorSpace],
+/// [ColorSpace::Corrected] is treated as [ColorSpace::LinearRgb].
+///
+/// ```
+/// use tiny_gradient::{interpolation::{ColorSpace, Spline}, Gradient, GradientStr};
+///
+/// let text = "Hello World!";
+///
+/// let colored = text
+///     .gradient(Gradient::Rainbow)
+///     .space(ColorSpace::Oklab)
+///     .spline(Spline::CatmullRom);
+///
+/// println!("{}", colored);
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Spline {
+    /// Each pair of colors is joined by a straight line.
+    ///
+    /// It's a default option.
+    #[default]
+    Linear,
+    /// Catmull-Rom spline, a smooth curve which goes through each color.
+    CatmullRom,
+    /// Uniform B-spline, a smoother curve which is only pulled towards each color.
+    ///
+    /// Only the first and the last colors are hit exactly.
+    BSpline,
+}
+
+/// A way a blended color is converted back to 8 bit components.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Rounding {
+    /// Drops a fractional part.
+    ///
+    /// It's a default option.
+    #[default]
+    Truncate,
+    /// Rounds to the nearest value.
+    Nearest,
+}
+
+/// A set of options used to blend colors.
+///
+/// The default settings give the same colors as [ColorSpace::Corrected] always did.
+///
+/// ```
+/// use tiny_gradient::{
+///     gradient::Gradient,
+///     interpolation::{ColorSpace, Interpolation, Rounding},
+///     RGB,
+/// };
+///
+/// let interpolation = Interpolation::new()
+///     .space(ColorSpace::Srgb)
+///     .rounding(Rounding::Nearest);
+///
+/// let gradient = Gradient::new(RGB::new(0, 0, 0), RGB::new(255, 255, 255), 3)
+///     .interpolation(interpolation);
+///
+/// assert_eq!(gradient.at(0.5), RGB::new(128, 128, 128));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Interpolation {
+    pub(crate) space: ColorSpace,
+    pub(crate) hue: HueInterpolation,
+    pub(crate) easing: Easing,
+    pub(crate) spline: Spline,
+    pub(crate) brightness: Option<Float>,
+    pub(crate) rounding: Rounding,
+}
+
+impl Interpolation {
+    /// Creates default settings.
+    pub const fn new() -> Self {
+        Self {
+            space: ColorSpace::Corrected,
+            hue: HueInterpolation::Shorter,
+            easing: Easing::Linear,
+            spline: Spline::Linear,
+            brightness: Some(Float(BRIGHTNESS_GAMMA)),
+            rounding: Rounding::Truncate,
+        }
+    }
+
+    /// Sets a color space in which colors are interpolated.
+    ///
+    /// Default is [ColorSpace::Corrected].
+    pub const fn space(mut self, space: ColorSpace) -> Self {
+        self.space = space;
+        self
+    }
+
+    /// Sets a direction of hue interpolation.
+    ///
+    /// Default is [HueInterpolation::Shorter].
+    pub const fn hue(mut self, hue: HueInterpolation) -> Self {
+        self.hue = hue;
+        self
+    }
+
+    /// Sets an easing applied to each pair of colors.
+    ///
+    /// Default is [Easing::Linear].
+    pub const fn easing(mut self, easing: Easing) -> Self {
+        self.easing = easing;
+        self
+    }
+
+    /// Sets a curve which joins colors together.
+    ///
+    /// Default is [Spline::Linear].
+    pub const fn spline(mut self, spline: Spline) -> Self {
+        self.spline = spline;
+        self
+    }
+
+    /// Sets a gamma of the brightness correction used by [ColorSpace::Corrected].
+    ///
+    /// [None] turns the correction off,
+    /// so [ColorSpace::Corrected] becomes the same as [ColorSpace::LinearRgb].
+    /// A gamma which is not a positive finite number turns it off as well.
+    ///
+    /// Default is `Some(0.43)`.
+    pub const fn brightness(mut self, gamma: Option<f32>) -> Self {
+        self.brightness = match gamma {
+            Some(gamma) if gamma > 0.0 && gamma.is_finite() => Some(Float(gamma)),
+            _ => None,
+        };
+        self
+    }
+
+    /// Sets a way colors are converted back to 8 bit components.
+    ///
+    /// Default is [Rounding::Truncate].
+    pub const fn rounding(mut self, rounding: Rounding) -> Self {
+        self.rounding = rounding;
+        self
+    }
+
+    // Mix [0..1]
+    //      0   --> all c1
+    //      1   --> all c2
+    //
+    // Colors are normalized to 0..1.
+    pub(crate) fn mix(&self, c1: RGB<f32>, c2: RGB<f32>, mix: f32) -> RGB<f32> {
+        let mix = self.easing.apply(mix);
+        self.blend(c1, c2, mix)
+    }
+
+    // Blends colors along with their alpha.
+    //
+    // Colors are weighted by alpha the same way premultiplied colors are,
+    // so a transparent color doesn't tint the other one.
+    pub(crate) fn mix_alpha(&self, c1: RGBA<f32>, c2: RGBA<f32>, mix: f32) -> RGBA<f32> {
+        let mix = self.easing.apply(mix);
+        let a = linear_interpolation(c1.a, c2.a, mix);
+        let weight = if a > 0.0 { mix * c2.a / a } else { mix };
+
+        let c = self.blend(c1.rgb(), c2.rgb(), weight);
+        RGBA::new(c.r, c.g, c.b, a)
+    }
+
+    // Converts a normalized color back using a chosen rounding.
+    pub(crate) fn quantize<T: Component>(&self, c: RGB<f32>) -> RGB<T> {
+        let convert = |c| self.rounding.convert(c);
+        RGB::new(convert(c.r), convert(c.g), convert(c.b))
+    }
+
+    pub(crate) fn quantize_alpha<T: Component>(&self, c: RGBA<f32>) -> RGBA<T> {
+        let convert = |c| self.rounding.convert(c);
+        RGBA::new(convert(c.r), convert(c.g), convert(c.b), convert(c.a))
+    }
+
+    // Blends a segment `p1..p2` of a spline,
+    // `p0` and `p3` are its neighbors if there are any.
+    //
+    // Points are colors with their positions,
+    // the segment must not be empty.
+    //
+    // Alpha goes along the same curve, but colors are not weighted by it.
+    pub(crate) fn mix_spline(
+        &self,
+        p0: Option<(RGBA<f32>, f32)>,
+        p1: (RGBA<f32>, f32),
+        p2: (RGBA<f32>, f32),
+        p3: Option<(RGBA<f32>, f32)>,
+        mix: f32,
+    ) -> RGBA<f32> {
+        if self.spline == Spline::Linear {
+            return self.mix_alpha(p1.0, p2.0, mix);
+        }
+
+        let mix = self.easing.apply(mix);
+        let space = match self.space {
+            ColorSpace::Corrected => ColorSpace::LinearRgb,
+            space => space,
+        };
+
+        let to = |c: RGBA<f32>| space.convert(c.rgb());
+        let (mut v1, x1) = (to(p1.0), p1.1);
+        let (mut v2, x2) = (to(p2.0), p2.1);
+
+        // neighbors behind a hard stop are not taken into account,
+        // the missing ones are reflected
+        let p0 = p0.filter(|&(_, x)| x < x1);
+        let p3 = p3.filter(|&(_, x)| x > x2);
+
+        let (mut v0, x0) = match p0 {
+            Some((c, x)) => (to(c), x),
+            None => ([f32::NAN; 3], 2.0 * x1 - x2),
+        };
+        let (mut v3, x3) = match p3 {
+            Some((c, x)) => (to(c), x),
+            None => ([f32::NAN; 3], 2.0 * x2 - x1),
+        };
+
+        let (a1, a2) = (p1.0.a, p2.0.a);
+        let a0 = p0.map_or(2.0 * a1 - a2, |(c, _)| c.a);
+        let a3 = p3.map_or(2.0 * a2 - a1, |(c, _)| c.a);
+
+        if let Some(i) = space.hue() {
+            match unwrap_hue(v1[i], v2[i], self.hue) {
+                Some((h1, h2)) => {
+                    v1[i] = h1;
+                    v2[i] = h2;
+                    v0[i] = nearest_hue(h1, v0[i]);
+                    v3[i] = nearest_hue(h2, v3[i]);
+                }
+                None => {
+                    v0[i] = 0.0;
+                    v1[i] = 0.0;
+                    v2[i] = 0.0;
+                    v3[i] = 0.0;
+                }
+            }
+        }
+
+        let mut c = [0.0; 3];
+        for i in 0..3 {
+            if v0[i].is_nan() {
+                v0[i] = 2.0 * v1[i] - v2[i];
+            }
+
+            if v3[i].is_nan() {
+                v3[i] = 2.0 * v2[i] - v1[i];
+            }
+
+            c[i] = match self.spline {
+                Spline::Linear => linear_interpolation(v1[i], v2[i], mix),
+                Spline::CatmullRom => {
+                    catmull_rom([v0[i], v1[i], v2[i], v3[i]], [x0, x1, x2, x3], mix)
+                }
+                Spline::BSpline => b_spline([v0[i], v1[i], v2[i], v3[i]], mix),
+            };
+        }
+
+        if let Some(i) = space.hue() {
+            c[i] = normalize_hue(c[i]);
+        }
+
+        let a = match self.spline {
+            Spline::Linear => linear_interpolation(a1, a2, mix),
+            Spline::CatmullRom => catmull_rom([a0, a1, a2, a3], [x0, x1, x2, x3], mix),
+            Spline::BSpline => b_spline([a0, a1, a2, a3], mix),
+        };
+
+        let c = clamp_rgb(space.convert_back(c));
+        RGBA::new(c.r, c.g, c.b, a.clamp(0.0, 1.0))
+    }
+
+    fn blend(&self, c1: RGB<f32>, c2: RGB<f32>, mix: f32) -> RGB<f32> {
+        let c = match self.space {
+            ColorSpace::Srgb => rgb_linear_interpolation(c1, c2, mix),
+            ColorSpace::Corrected => {
+                mix_corrected(c1, c2, mix, self.brightness.map(|gamma| gamma.0))
+            }
+            space => {
+                let mut c1 = space.convert(c1);
+                let mut c2 = space.convert(c2);
+
+                if let Some(i) = space.hue() {
+                    match unwrap_hue(c1[i], c2[i], self.hue) {
+                        Some((h1, h2)) => {
+                            c1[i] = h1;
+                            c2[i] = h2;
+                        }
+                        None => {
+                            c1[i] = f32::NAN;
+                            c2[i] = f32::NAN;
+                        }
+                    }
+                }
+
+                let mut c = [0.0; 3];
+                for i in 0..3 {
+                    c[i] = linear_interpolation(c1[i], c2[i], mix);
+                }
+
+                if let Some(i) = space.hue() {
+                    c[i] = normalize_hue(c[i]);
+                }
+
+                space.convert_back(c)
+            }
+        };
+
+        clamp_rgb(c)
+    }
+}
+
+impl ColorSpace {
+    // Converts a normalized sRGB color into the space.
+    fn convert(self, c: RGB<f32>) -> [f32; 3] {
+        match self {
+            ColorSpace::Srgb => [c.r, c.g, c.b],
+            ColorSpace::LinearRgb | ColorSpace::Corrected => space::srgb_to_linear(c),
+            ColorSpace::Hsl => space::srgb_to_hsl(c),
+            ColorSpace::Hsv => space::srgb_to_hsv(c),
+            ColorSpace::Lab => space::srgb_to_lab(c),
+            ColorSpace::Lch => space::srgb_to_lch(c),
+            ColorSpace::Oklab => space::srgb_to_oklab(c),
+            ColorSpace::Oklch => space::srgb_to_oklch(c),
+        }
+    }
+
+    fn convert_back(self, c: [f32; 3]) -> RGB<f32> {
+        match self {
+            ColorSpace::Srgb => RGB::new(c[0], c[1], c[2]),
+            ColorSpace::LinearRgb | ColorSpace::Corrected => space::linear_to_srgb(c),
+            ColorSpace::Hsl => space::hsl_to_srgb(c),
+            ColorSpace::Hsv => space::hsv_to_srgb(c),
+            ColorSpace::Lab => space::lab_to_srgb(c),
+            ColorSpace::Lch => space::lch_to_srgb(c),
+            ColorSpace::Oklab => space::oklab_to_srgb(c),
+            ColorSpace::Oklch => space::oklch_to_srgb(c),
+        }
+    }
+
+    // An index of a hue component for cylindrical spaces.
+    fn hue(self) -> Option<usize> {
+        match self {
+            ColorSpace::Hsl | ColorSpace::Hsv => Some(0),
+            ColorSpace::Lch | ColorSpace::Oklch => Some(2),
+            _ => None,
+        }
+    }
+}
+
+// Moves hues apart so a linear interpolation between them goes in a given direction.
+// A missing hue takes a value of the other one.
+fn unwrap_hue(h1: f32, h2: f32, direction: HueInterpolation) -> Option<(f32, f32)> {
+    let (mut h1, mut h2) = match (h1.is_nan(), h2.is_nan()) {
+        (true, true) => return None,
+        (true, false) => (h2, h2),
+        (false, true) => (h1, h1),
+        (false, false) => (h1, h2),
+    };
+
+    let diff = h2 - h1;
+    match direction {
+        HueInterpolation::Shorter if diff > 180.0 => h1 += 360.0,
+        HueInterpolation::Shorter if diff < -180.0 => h2 += 360.0,
+        HueInterpolation::Longer if diff > 0.0 && diff < 180.0 => h1 += 360.0,
+        HueInterpolation::Longer if diff > -180.0 && diff <= 0.0 => h2 += 360.0,
+        HueInterpolation::Increasing if diff < 0.0 => h2 += 360.0,
+        HueInterpolation::Decreasing if diff > 0.0 => h1 += 360.0,
+        _ => {}
+    }
+
+    Some((h1, h2))
+}
+
+// Returns a hue equal to `h` which is the closest to `base`.
+fn nearest_hue(base: f32, h: f32) -> f32 {
+    if h.is_nan() {
+        return h;
+    }
+
+    let mut diff = normalize_hue(h - base);
+    if diff > 180.0 {
+        diff -= 360.0;
+    }
+
+    base + diff
+}
+
+// Hermite form of Catmull-Rom spline which respects uneven positions of points.
+fn catmull_rom(v: [f32; 4], x: [f32; 4], s: f32) -> f32 {
+    let h = x[2] - x[1];
+    let m1 = (v[2] - v[0]) / (x[2] - x[0]);
+    let m2 = (v[3] - v[1]) / (x[3] - x[1]);
+
+    let s2 = s * s;
+    let s3 = s2 * s;
+
+    (2.0 * s3 - 3.0 * s2 + 1.0) * v[1]
+        + (s3 - 2.0 * s2 + s) * h * m1
+        + (-2.0 * s3 + 3.0 * s2) * v[2]
+        + (s3 - s2) * h * m2
+}
+
+fn b_spline(v: [f32; 4], s: f32) -> f32 {
+    let s2 = s * s;
+    let s3 = s2 * s;
+
+    ((1.0 - 3.0 * s + 3.0 * s2 - s3) * v[0]
+        + (4.0 - 6.0 * s2 + 3.0 * s3) * v[1]
+        + (1.0 + 3.0 * s + 3.0 * s2 - 3.0 * s3) * v[2]
+        + s3 * v[3])
+        / 6.0
+}
+
+impl Default for Interpolation {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Rounding {
+    fn convert<T: Component>(self, c: f32) -> T {
+        match self {
+            Rounding::Truncate => T::truncate_normalized(c),
+            Rounding::Nearest => T::from_normalized(c),
+        }
+    }
+}
+
+// Solves a CSS cubic bezier curve for a given x.
+fn cubic_bezier(x1: f32, y1: f32, x2: f32, y2: f32, x: f32) -> f32 {
+    let (x1, x2) = (x1.clamp(0.0, 1.0), x2.clamp(0.0, 1.0));
+    let bezier = |p1: f32, p2: f32, s: f32| {
+        let r = 1.0 - s;
+        3.0 * p1 * r * r * s + 3.0 * p2 * r * s * s + s * s * s
+    };
+
+    // x(s) is monotonic when x1 and x2 are in [0, 1] so a bisection is enough
+    let (mut lo, mut hi) = (0.0, 1.0);
+    for _ in 0..24 {
+        let mid = (lo + hi) / 2.0;
+        if bezier(x1, x2, mid) < x {
+            lo = mid;
+        } else {
+            hi = mid;
+        }
+    }
+
+    bezier(y1, y2, (lo + hi) / 2.0)
+}
+
+fn clamp_rgb(c: RGB<f32>) -> RGB<f32> {
+    let clamp = |v: f32| if v.is_nan() { 0.0 } else { v.clamp(0.0, 1.0) };
+    RGB::new(clamp(c.r), clamp(c.g), clamp(c.b))
+}
+
+#[cfg(test)]
+mod tests {
+    use libm::fabsf;
+
+    use crate::gradient::normalize_rgb;
+
+    use super::{
+        linear_interpolation, normalize_hue, unwrap_hue, ColorSpace, Easing, HueInterpolation,
+        Interpolation, Rounding, Spline, RGB, RGBA,
+    };
+
+    const RED: RGB = RGB::new(255, 0, 0);
+    const BLUE: RGB = RGB::new(0, 0, 255);
+
+    #[test]
+    fn mix_test() {
+        let spaces = [
+            ColorSpace::Srgb,
+            ColorSpace::LinearRgb,
+            ColorSpace::Corrected,
+            ColorSpace::Hsl,
+            ColorSpace::Hsv,
+            ColorSpace::Lab,
+            ColorSpace::Lch,
+            ColorSpace::Oklab,
+            ColorSpace::Oklch,
+        ];
+
+        for space in spaces {
+            let interpolation = Interpolation {
+                space,
+                ..Default::default()
+            };
+            assert_eq!(interpolation.mix_rgb(RED, BLUE, 0.0), RED, "{:?}", space);
+            assert_eq!(interpolation.mix_rgb(RED, BLUE, 1.0), BLUE, "{:?}", space);
+        }
+    }
+
+    #[test]
+    fn mix_space_test() {
+        let mix = |space| {
+            Interpolation {
+                space,
+                ..Default::default()
+            }
+            .mix_rgb(RED, BLUE, 0.5)
+        };
+
+        assert_eq!(mix(ColorSpace::Srgb), RGB::new(127, 0, 127));
+        assert_eq!(mix(ColorSpace::LinearRgb), RGB::new(188, 0, 188));
+        assert_eq!(mix(ColorSpace::Hsl), RGB::new(255, 0, 255));
+        assert_eq!(mix(ColorSpace::Oklab), RGB::new(140, 83, 162));
+        assert_eq!(mix(ColorSpace::Oklch), RGB::new(186, 0, 194));
+    }
+
+    #[test]
+    fn spline_test() {
+        const GREEN: RGB = RGB::new(0, 255, 0);
+
+        let spaces = [ColorSpace::Srgb, ColorSpace::Oklab, ColorSpace::Oklch];
+        for space in spaces {
+            let mix = |spline, mix| {
+                Interpolation {
+                    space,
+                    spline,
+                    ..Default::default()
+                }
+                .mix_spline_rgb([Some(RED), Some(GREEN), Some(BLUE), None], mix)
+            };
+
+            assert_eq!(mix(Spline::CatmullRom, 0.0), GREEN);
+            assert_eq!(mix(Spline::CatmullRom, 1.0), BLUE);
+            assert_ne!(mix(Spline::BSpline, 0.0), GREEN);
+            assert_eq!(mix(Spline::BSpline, 1.0), BLUE);
+            assert_eq!(
+                mix(Spline::Linear, 0.5),
+                Interpolation {
+                    space,
+                    ..Default::default()
+                }
+                .mix_rgb(GREEN, BLUE, 0.5)
+            );
+        }
+
+        // two colors make a straight line
+        let interpolation = Interpolation {
+            space: ColorSpace::Srgb,
+            spline: Spline::CatmullRom,
+            ..Default::default()
+        };
+        assert_eq!(
+            interpolation.mix_spline_rgb([None, Some(RED), Some(BLUE), None], 0.25),
+            RGB::new(191, 0, 63),
+        );
+    }
+
+    #[test]
+    fn mix_hue_test() {
+        use HueInterpolation::*;
+
+        assert_eq!(mix_hue(350.0, 10.0, 0.5, Shorter), 0.0);
+        assert_eq!(mix_hue(10.0, 350.0, 0.25, Shorter), 5.0);
+        assert_eq!(mix_hue(f32::NAN, 90.0, 0.5, Shorter), 90.0);
+        assert!(mix_hue(f32::NAN, f32::NAN, 0.5, Shorter).is_nan());
+
+        assert_eq!(mix_hue(350.0, 10.0, 0.5, Longer), 180.0);
+        assert_eq!(mix_hue(0.0, 0.0, 0.5, Longer), 180.0);
+        assert_eq!(mix_hue(0.0, 240.0, 0.5, Longer), 120.0);
+        assert_eq!(mix_hue(0.0, 240.0, 0.5, Increasing), 120.0);
+        assert_eq!(mix_hue(240.0, 0.0, 0.5, Increasing), 300.0);
+        assert_eq!(mix_hue(0.0, 240.0, 0.5, Decreasing), 300.0);
+        assert_eq!(mix_hue(240.0, 0.0, 0.5, Decreasing), 120.0);
+    }
+
+    #[test]
+    fn mix_hue_direction_test() {
+        let mix = |hue| {
+            Interpolation {
+                space: ColorSpace::Hsl,
+                hue,
+                ..Default::default()
+            }
+            .mix_rgb(RED, BLUE, 0.5)
+        };
+
+        assert_eq!(mix(HueInterpolation::Shorter), RGB::new(255, 0, 255));
+        assert_eq!(mix(HueInterpolation::Longer), RGB::new(0, 255, 0));
+        assert_eq!(mix(HueInterpolation::Increasing), RGB::new(0, 255, 0));
+        assert_eq!(mix(HueInterpolation::Decreasing), RGB::new(255, 0, 255));
+    }
+
+    #[test]
+    fn brightness_test() {
+        const BLACK: RGB = RGB::new(0, 0, 0);
+        const WHITE: RGB = RGB::new(255, 255, 255);
+
+        let mix = |interpolation: Interpolation| interpolation.mix_rgb(BLACK, WHITE, 0.5);
+
+        assert_eq!(mix(Interpolation::new()), RGB::new(123, 123, 123));
+        assert_eq!(
+            mix(Interpolation::new().brightness(Some(0.5))),
+            RGB::new(137, 137, 137)
+        );
+        assert_eq!(
+            mix(Interpolation::new().brightness(None)),
+            mix(Interpolation::new().space(ColorSpace::LinearRgb))
+        );
+        for gamma in [0.0, -0.43, f32::NAN, f32::INFINITY] {
+            assert_eq!(
+                Interpolation::new().brightness(Some(gamma)),
+                Interpolation::new().brightness(None)
+            );
+        }
+        assert_eq!(
+            mix(Interpolation::new().space(ColorSpace::Srgb)),
+            RGB::new(127, 127, 127)
+        );
+        assert_eq!(
+            mix(Interpolation::new()
+                .space(ColorSpace::Srgb)
+                .rounding(Rounding::Nearest)),
+            RGB::new(128, 128, 128)
+        );
+    }
+
+    #[test]
+    fn easing_test() {
+        let easings = [
+            Easing::Linear,
+            Easing::Ease,
+            Easing::EaseIn,
+            Easing::EaseOut,
+            Easing::EaseInOut,
+            Easing::PowerIn(3.0),
+            Easing::PowerOut(3.0),
+            Easing::PowerInOut(3.0),
+            Easing::SineIn,
+            Easing::SineOut,
+            Easing::SineInOut,
+            Easing::ExpoIn,
+            Easing::ExpoOut,
+            Easing::ExpoInOut,
+            Easing::Smoothstep,
+            Easing::CubicBezier(0.1, 0.7, 1.0, 0.1),
+        ];
+
+        for easing in easings {
+            assert!(fabsf(easing.apply(0.0)) < 1e-3, "{:?}", easing);
+            assert!(fabsf(easing.apply(1.0) - 1.0) < 1e-3, "{:?}", easing);
+        }
+
+        assert_eq!(Easing::Linear.apply(0.3), 0.3);
+        assert_eq!(Easing::Smoothstep.apply(0.5), 0.5);
+        assert_eq!(Easing::PowerOut(2.0).apply(0.5), 0.75);
+        assert_eq!(Easing::PowerInOut(2.0).apply(0.25), 0.125);
+        assert!(fabsf(Easing::EaseInOut.apply(0.5) - 0.5) < 1e-3);
+        assert!(fabsf(Easing::CubicBezier(0.0, 0.0, 1.0, 1.0).apply(0.3) - 0.3) < 1e-3);
+        assert!(Easing::EaseIn.apply(0.5) < 0.5);
+        assert!(Easing::EaseOut.apply(0.5) > 0.5);
+        assert_eq!(Easing::Linear.apply(f32::NAN), 0.0);
+        assert_eq!(Easing::Linear.apply(2.0), 1.0);
+
+        assert!(easings.windows(2).all(|pair| pair[0] < pair[1]));
+        assert!(Easing::PowerIn(1.0) < Easing::PowerIn(2.0));
+        assert_eq!(Easing::PowerIn(f32::NAN), Easing::PowerIn(f32::NAN));
+        assert_ne!(Easing::PowerIn(2.0), Easing::PowerOut(2.0));
+    }
+
+    fn mix_hue(h1: f32, h2: f32, mix: f32, direction: HueInterpolation) -> f32 {
+        match unwrap_hue(h1, h2, direction) {
+            Some((h1, h2)) => normalize_hue(linear_interpolation(h1, h2, mix)),
+            None => f32::NAN,
+        }
+    }
+
+    #[test]
+    fn mix_alpha_test() {
+        let interpolation = Interpolation::new().space(ColorSpace::Srgb);
+        let red = RGBA::new(1.0, 0.0, 0.0, 1.0);
+        let transparent = RGBA::new(0.0, 0.0, 0.0, 0.0);
+
+        assert_eq!(
+            interpolation.mix_alpha(red, transparent, 0.5),
+            RGBA::new(1.0, 0.0, 0.0, 0.5)
+        );
+        assert_eq!(
+            interpolation.mix_alpha(transparent, transparent, 0.5),
+            transparent
+        );
+
+        let blue = RGBA::new(0.0, 0.0, 1.0, 0.5);
+        let c = interpolation.mix_alpha(red, blue, 0.5);
+        assert!(fabsf(c.r - 2.0 / 3.0) < 1e-6, "{:?}", c);
+        assert!(fabsf(c.a - 0.75) < 1e-6, "{:?}", c);
+    }
+
+    impl Interpolation {
+        fn mix_rgb(&self, c1: RGB, c2: RGB, mix: f32) -> RGB {
+            self.quantize(self.mix(normalize_rgb(c1), normalize_rgb(c2), mix))
+        }
+
+        // Colors are placed evenly,
+        // the segment is between the 2nd and the 3rd ones.
+        fn mix_spline_rgb(&self, colors: [Option<RGB>; 4], mix: f32) -> RGB {
+            let [p0, p1, p2, p3] = colors;
+            let point = |c: RGB, x: f32| {
+                let c = normalize_rgb(c);
+                (RGBA::new(c.r, c.g, c.b, 1.0), x)
+            };
+
+            let c = self.mix_spline(
+                p0.map(|c| point(c, 0.0)),
+                point(p1.unwrap(), 0.5),
+                point(p2.unwrap(), 1.0),
+                p3.map(|c| point(c, 1.5)),
+                mix,
+            );
+
+            self.quantize(c.rgb())
+        }
+    }
+}
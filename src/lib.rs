@@ -24,6 +24,33 @@
 //! println!("{}", colored);
 //! ```
 //!
+//! ### Color stops usage
+//!
+//! ```
+//! use tiny_gradient::{stops::ColorStop, GradientStr, RGB};
+//!
+//! let text = "Hello World!";
+//!
+//! let colored = text.gradient([
+//!     ColorStop::new(RGB::new(0xFF, 0x00, 0x00), 0.0),
+//!     ColorStop::new(RGB::new(0x00, 0x00, 0xFF), 0.3),
+//!     ColorStop::new(RGB::new(0x00, 0xFF, 0x00), 1.0),
+//! ]);
+//!
+//! println!("{}", colored);
+//! ```
+//!
+//! ### Sampling usage
+//!
+//! ```
+//! use tiny_gradient::Gradient;
+//!
+//! let progress = 0.7;
+//! let color = Gradient::Rainbow.at(progress);
+//!
+//! println!("{:?}", color);
+//! ```
+//!
 //! ### Generator usage
 //!
 //! ```
@@ -44,12 +71,31 @@
     rust_2021_prelude_collisions
 )]
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+mod color;
 mod display;
 mod gradients;
+mod ops;
+mod parse;
+mod random;
 mod rgb;
+mod rgba;
+mod space;
 
+pub mod contrast;
+pub mod css;
+pub mod cvd;
 pub mod gradient;
+pub mod harmony;
+pub mod interpolation;
+pub mod palette;
+pub mod quantize;
+pub mod stops;
 
-pub use crate::display::{GradientDisplay, GradientStr};
+pub use crate::color::{Hsl, Hsv, Hwb, Lab, Lch, LinearRgb, Oklab, Oklch};
+pub use crate::display::{DynGradientDisplay, GradientDisplay, GradientStr};
 pub use gradients::Gradient;
-pub use rgb::RGB;
+pub use rgb::{Component, ParseRGBError, RGBErrorKind, RGB};
+pub use rgba::RGBA;
@@ -0,0 +1,150 @@
+//! This module contains operations on [RGB] colors.
+//!
+//! Lightness, saturation and hue are changed in HSL color space.
+
+use crate::{
+    gradient::{mix_color, srgb_apply_companding_color},
+    space::{hsl_to_srgb, luminance, normalize_hue, srgb_to_hsl},
+    RGB,
+};
+
+impl RGB {
+    /// Mixes 2 colors the same way a gradient does by default,
+    /// `0` gives this color and `1` gives the other one.
+    ///
+    /// ```
+    /// use tiny_gradient::RGB;
+    ///
+    /// let black = RGB::new(0, 0, 0);
+    /// let white = RGB::new(255, 255, 255);
+    ///
+    /// assert_eq!(black.mix(white, 0.0), black);
+    /// assert_eq!(black.mix(white, 0.5), RGB::new(123, 123, 123));
+    /// ```
+    pub fn mix(self, other: RGB, ratio: f32) -> RGB {
+        mix_color(self, other, ratio.clamp(0.0, 1.0))
+    }
+
+    /// Mixes the color with white.
+    pub fn tint(self, ratio: f32) -> RGB {
+        self.mix(RGB::new(255, 255, 255), ratio)
+    }
+
+    /// Mixes the color with black.
+    pub fn shade(self, ratio: f32) -> RGB {
+        self.mix(RGB::new(0, 0, 0), ratio)
+    }
+
+    /// Increases HSL lightness by `amount` in [0, 1] range.
+    ///
+    /// ```
+    /// use tiny_gradient::RGB;
+    ///
+    /// assert_eq!(RGB::new(255, 0, 0).lighten(0.25), RGB::new(255, 128, 128));
+    /// assert_eq!(RGB::new(255, 0, 0).darken(0.25), RGB::new(128, 0, 0));
+    /// ```
+    pub fn lighten(self, amount: f32) -> RGB {
+        self.adjust_hsl(|[h, s, l]| [h, s, l + amount])
+    }
+
+    /// Decreases HSL lightness by `amount` in [0, 1] range.
+    pub fn darken(self, amount: f32) -> RGB {
+        self.lighten(-amount)
+    }
+
+    /// Increases HSL saturation by `amount` in [0, 1] range.
+    pub fn saturate(self, amount: f32) -> RGB {
+        self.adjust_hsl(|[h, s, l]| [h, s + amount, l])
+    }
+
+    /// Decreases HSL saturation by `amount` in [0, 1] range.
+    pub fn desaturate(self, amount: f32) -> RGB {
+        self.saturate(-amount)
+    }
+
+    /// Rotates a hue by a given angle in degrees.
+    ///
+    /// ```
+    /// use tiny_gradient::RGB;
+    ///
+    /// assert_eq!(RGB::new(255, 0, 0).rotate_hue(120.0), RGB::new(0, 255, 0));
+    /// ```
+    pub fn rotate_hue(self, degrees: f32) -> RGB {
+        self.adjust_hsl(|[h, s, l]| [normalize_hue(h + degrees), s, l])
+    }
+
+    /// Returns an opposite color.
+    pub const fn invert(self) -> RGB {
+        RGB::new(255 - self.r, 255 - self.g, 255 - self.b)
+    }
+
+    /// Returns a gray with the same relative luminance.
+    ///
+    /// ```
+    /// use tiny_gradient::RGB;
+    ///
+    /// assert_eq!(RGB::new(0, 0, 255).grayscale(), RGB::new(76, 76, 76));
+    /// ```
+    pub fn grayscale(self) -> RGB {
+        let y = srgb_apply_companding_color(luminance(self.convert()));
+        RGB::new(y, y, y).convert()
+    }
+
+    fn adjust_hsl(self, f: impl FnOnce([f32; 3]) -> [f32; 3]) -> RGB {
+        let [h, s, l] = f(srgb_to_hsl(self.convert()));
+        hsl_to_srgb([h, s.clamp(0.0, 1.0), l.clamp(0.0, 1.0)]).convert()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{space::luminance, RGB};
+
+    const COLORS: [RGB; 4] = [
+        RGB::new(0, 0, 0),
+        RGB::new(255, 255, 255),
+        RGB::new(18, 200, 97),
+        RGB::new(63, 81, 177),
+    ];
+
+    #[test]
+    fn identity_test() {
+        for c in COLORS {
+            assert_eq!(c.lighten(0.0), c);
+            assert_eq!(c.saturate(0.0), c);
+            assert_eq!(c.rotate_hue(360.0), c);
+            assert_eq!(c.mix(RGB::new(1, 2, 3), 0.0), c);
+            assert_eq!(c.mix(RGB::new(1, 2, 3), 1.0), RGB::new(1, 2, 3));
+            assert_eq!(c.invert().invert(), c);
+        }
+    }
+
+    #[test]
+    fn mix_test() {
+        let black = RGB::new(0, 0, 0);
+        let white = RGB::new(255, 255, 255);
+
+        assert_eq!(black.mix(white, 0.5), RGB::new(123, 123, 123));
+        assert_eq!(black.mix(white, 0.25), RGB::new(56, 56, 56));
+        assert_eq!(black.mix(white, -1.0), black);
+        assert_eq!(black.mix(white, 2.0), white);
+        assert_eq!(white.mix(black, 0.5), black.mix(white, 0.5));
+    }
+
+    #[test]
+    fn adjust_test() {
+        let c = RGB::new(63, 81, 177);
+
+        assert_eq!(c.lighten(1.0), RGB::new(255, 255, 255));
+        assert_eq!(c.darken(1.0), RGB::new(0, 0, 0));
+        assert_eq!(c.desaturate(1.0), RGB::new(120, 120, 120));
+        assert_eq!(c.invert(), RGB::new(192, 174, 78));
+        assert_eq!(c.tint(1.0), RGB::new(255, 255, 255));
+        assert_eq!(c.shade(1.0), RGB::new(0, 0, 0));
+
+        let gray = c.grayscale();
+        assert!(gray.r == gray.g && gray.g == gray.b);
+        let diff = luminance(gray.convert()) - luminance(c.convert());
+        assert!(diff.abs() < 2e-3, "{}", diff);
+    }
+}
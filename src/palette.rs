@@ -0,0 +1,897 @@
+//! A module contains [Palette] a list of color stops with combinators.
+//!
+//! ```
+//! use tiny_gradient::{palette::Palette, Gradient, GradientStr};
+//!
+//! let palette = Gradient::Rainbow
+//!     .slice(0.2, 0.8)
+//!     .concat(Gradient::Forest.reversed())
+//!     .repeat(2);
+//!
+//! println!("{}", "Hello World!".gradient(palette));
+//! ```
+
+use core::{fmt, iter::FusedIterator};
+
+#[cfg(feature = "alloc")]
+use alloc::{boxed::Box, rc::Rc, vec::Vec};
+
+#[cfg(all(feature = "alloc", target_has_atomic = "ptr"))]
+use alloc::sync::Arc;
+
+use crate::{
+    contrast::is_darker_side,
+    cvd::{Deficiency, Simulate},
+    interpolation::Interpolation,
+    stops::{ColorStop, Positions, Stops},
+    RGB, RGBA,
+};
+
+/// A list of color stops with random access.
+///
+/// Offsets of combined palettes are resolved the same way [Stops] does.
+///
+/// The trait is object safe,
+/// so palettes loaded at runtime can be stored as `&dyn Palette` or `Arc<dyn Palette>`.
+pub trait Palette {
+    /// Returns a number of stops.
+    fn len(&self) -> usize;
+
+    /// Returns a stop by its index which is less then [Palette::len].
+    fn stop(&self, i: usize) -> ColorStop;
+
+    /// Checks whether there's no stops.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns a resolved offset of a stop, the one [Stops] would use.
+    ///
+    /// By default all stops up to the given one are walked through,
+    /// combinators compute it from offsets of their palettes instead.
+    fn offset(&self, i: usize) -> f32 {
+        Positions::new(PaletteIter::new(self))
+            .nth(i)
+            .and_then(|stop| stop.offset)
+            .unwrap_or_default()
+    }
+
+    /// Flips the palette so it goes from the end to the beginning.
+    fn reversed(self) -> Reverse<Self>
+    where
+        Self: Sized,
+    {
+        Reverse { palette: self }
+    }
+
+    /// Takes a part of the palette between 2 positions in [0, 1] range
+    /// and stretches it to the whole line.
+    ///
+    /// Colors at the edges are interpolated with default settings.
+    ///
+    /// ```
+    /// use tiny_gradient::{palette::Palette, Gradient};
+    ///
+    /// let palette = Gradient::Rainbow.slice(0.2, 0.8);
+    ///
+    /// assert_eq!(palette.stop(0).color, Gradient::Rainbow.at(0.2));
+    /// ```
+    fn slice(self, from: f32, to: f32) -> Slice<Self>
+    where
+        Self: Sized,
+    {
+        Slice::new(self, from, to)
+    }
+
+    /// Appends another palette.
+    ///
+    /// Palettes take a part of the line proportional to a number of their stops.
+    fn concat<P>(self, other: P) -> Concat<Self, P>
+    where
+        Self: Sized,
+        P: Palette,
+    {
+        Concat {
+            first: self,
+            second: other,
+        }
+    }
+
+    /// Shifts colors `n` stops to the beginning, the first ones go to the end.
+    ///
+    /// Offsets are kept in place.
+    fn rotate(self, n: usize) -> Rotate<Self>
+    where
+        Self: Sized,
+    {
+        Rotate { palette: self, n }
+    }
+
+    /// Changes each color of the palette, alpha and offsets are kept.
+    ///
+    /// ```
+    /// use tiny_gradient::{palette::Palette, Gradient, GradientStr};
+    ///
+    /// // for light terminals
+    /// let summer = Gradient::Summer.map_colors(|c| c.darken(0.1));
+    ///
+    /// println!("{}", "Hello World!".gradient(summer));
+    /// ```
+    fn map_colors<F>(self, f: F) -> MapColors<Self, F>
+    where
+        Self: Sized,
+        F: Fn(RGB) -> RGB,
+    {
+        MapColors { palette: self, f }
+    }
+
+    /// Adjusts lightness of colors so they have at least
+    /// a given WCAG 2 contrast ratio against a background.
+    ///
+    /// Each pair of stops is split into 8 parts blended with default settings.
+    /// All of them are kept on one side of the background and adjusted to a 10% higher ratio,
+    /// so colors interpolated between them stay close to the ratio, though it's not guaranteed.
+    ///
+    /// It holds only if a hue is interpolated the shorter way, which is a default one,
+    /// or isn't interpolated at all as in rectangular color spaces.
+    /// Other [HueInterpolation](crate::interpolation::HueInterpolation) directions
+    /// go around the hue wheel between neighbor parts, so they can lose most of the contrast.
+    /// [GradientDisplay::ensure_contrast](crate::GradientDisplay::ensure_contrast)
+    /// adjusts printed colors instead.
+    ///
+    /// See [RGB::ensure_contrast].
+    ///
+    /// ```
+    /// use tiny_gradient::{contrast, palette::Palette, Gradient, GradientStr, RGB};
+    ///
+    /// let white = RGB::new(0xFF, 0xFF, 0xFF);
+    /// let pastel = Gradient::Pastel.ensure_contrast(white, 4.5);
+    ///
+    /// assert!(contrast::min_contrast(&pastel, white) >= 4.5);
+    ///
+    /// println!("{}", "Hello World!".gradient(pastel));
+    /// ```
+    fn ensure_contrast(self, background: RGB, ratio: f32) -> EnsureContrast<Self>
+    where
+        Self: Sized,
+    {
+        EnsureContrast {
+            palette: self,
+            background,
+            ratio,
+        }
+    }
+
+    /// Shows the palette the way it's seen with a color vision deficiency.
+    ///
+    /// See [Simulate].
+    fn simulate(self, deficiency: Deficiency) -> Simulate<Self>
+    where
+        Self: Sized,
+    {
+        Simulate::new(self, deficiency)
+    }
+
+    /// Squeezes the palette and repeats it `n` times along the line.
+    ///
+    /// A number of stops is limited by [usize::MAX].
+    fn repeat(self, n: usize) -> Repeat<Self>
+    where
+        Self: Sized,
+    {
+        Repeat { palette: self, n }
+    }
+}
+
+impl<P> Palette for &P
+where
+    P: Palette + ?Sized,
+{
+    fn len(&self) -> usize {
+        P::len(self)
+    }
+
+    fn stop(&self, i: usize) -> ColorStop {
+        P::stop(self, i)
+    }
+
+    fn offset(&self, i: usize) -> f32 {
+        P::offset(self, i)
+    }
+}
+
+impl<T> Palette for [T]
+where
+    T: Into<ColorStop> + Copy,
+{
+    fn len(&self) -> usize {
+        <[T]>::len(self)
+    }
+
+    fn stop(&self, i: usize) -> ColorStop {
+        self[i].into()
+    }
+}
+
+impl<T, const N: usize> Palette for [T; N]
+where
+    T: Into<ColorStop> + Copy,
+{
+    fn len(&self) -> usize {
+        N
+    }
+
+    fn stop(&self, i: usize) -> ColorStop {
+        self[i].into()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> Palette for Vec<T>
+where
+    T: Into<ColorStop> + Copy,
+{
+    fn len(&self) -> usize {
+        Vec::len(self)
+    }
+
+    fn stop(&self, i: usize) -> ColorStop {
+        self[i].into()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<P> Palette for Box<P>
+where
+    P: Palette + ?Sized,
+{
+    fn len(&self) -> usize {
+        P::len(self)
+    }
+
+    fn stop(&self, i: usize) -> ColorStop {
+        P::stop(self, i)
+    }
+
+    fn offset(&self, i: usize) -> f32 {
+        P::offset(self, i)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<P> Palette for Rc<P>
+where
+    P: Palette + ?Sized,
+{
+    fn len(&self) -> usize {
+        P::len(self)
+    }
+
+    fn stop(&self, i: usize) -> ColorStop {
+        P::stop(self, i)
+    }
+
+    fn offset(&self, i: usize) -> f32 {
+        P::offset(self, i)
+    }
+}
+
+#[cfg(all(feature = "alloc", target_has_atomic = "ptr"))]
+impl<P> Palette for Arc<P>
+where
+    P: Palette + ?Sized,
+{
+    fn len(&self) -> usize {
+        P::len(self)
+    }
+
+    fn stop(&self, i: usize) -> ColorStop {
+        P::stop(self, i)
+    }
+
+    fn offset(&self, i: usize) -> f32 {
+        P::offset(self, i)
+    }
+}
+
+/// A palette which goes in an opposite direction.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Reverse<P> {
+    palette: P,
+}
+
+impl<P: Palette> Palette for Reverse<P> {
+    fn len(&self) -> usize {
+        self.palette.len()
+    }
+
+    fn stop(&self, i: usize) -> ColorStop {
+        let mut stop = self.palette.stop(self.palette.len() - 1 - i);
+        stop.offset = Some(self.offset(i));
+        stop
+    }
+
+    fn offset(&self, i: usize) -> f32 {
+        1.0 - self.palette.offset(self.palette.len() - 1 - i)
+    }
+}
+
+/// A part of a palette.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Slice<P> {
+    palette: P,
+    from: f32,
+    to: f32,
+    // stops which are strictly inside the part
+    first: usize,
+    count: usize,
+    // colors at the edges
+    edges: Option<(ColorStop, ColorStop)>,
+}
+
+impl<P: Palette> Slice<P> {
+    fn new(palette: P, from: f32, to: f32) -> Self {
+        let from = if from.is_nan() {
+            0.0
+        } else {
+            from.clamp(0.0, 1.0)
+        };
+        let to = if to.is_nan() {
+            1.0
+        } else {
+            to.clamp(from, 1.0)
+        };
+
+        let mut first = 0;
+        let mut count = 0;
+        for (i, stop) in Positions::new(PaletteIter::new(&palette)).enumerate() {
+            let offset = stop.offset.unwrap_or_default();
+            if offset <= from {
+                first = i + 1;
+            } else if offset < to {
+                count += 1;
+            }
+        }
+
+        let stops = Stops::new(PaletteIter::new(&palette));
+        let cut = |t: f32, offset: f32| Some(ColorStop::from((stops.at_rgba(t)?, offset)));
+        let edges = cut(from, 0.0).zip(cut(to, 1.0));
+
+        Self {
+            palette,
+            from,
+            to,
+            first,
+            count,
+            edges,
+        }
+    }
+}
+
+impl<P: Palette> Palette for Slice<P> {
+    fn len(&self) -> usize {
+        if self.palette.is_empty() {
+            0
+        } else {
+            self.count + 2
+        }
+    }
+
+    fn stop(&self, i: usize) -> ColorStop {
+        let (start, end) = self.edges.expect("a palette is not empty");
+        if i == 0 {
+            return start;
+        }
+
+        if i == self.count + 1 {
+            return end;
+        }
+
+        let mut stop = self.palette.stop(self.first + i - 1);
+        stop.offset = Some(self.offset(i));
+        stop
+    }
+
+    fn offset(&self, i: usize) -> f32 {
+        if i == 0 {
+            return 0.0;
+        }
+
+        if i == self.count + 1 {
+            return 1.0;
+        }
+
+        let offset = self.palette.offset(self.first + i - 1);
+        (offset - self.from) / (self.to - self.from)
+    }
+}
+
+/// A palette followed by another one.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Concat<P1, P2> {
+    first: P1,
+    second: P2,
+}
+
+impl<P1: Palette, P2: Palette> Palette for Concat<P1, P2> {
+    fn len(&self) -> usize {
+        self.first.len() + self.second.len()
+    }
+
+    fn stop(&self, i: usize) -> ColorStop {
+        let n = self.first.len();
+        let mut stop = if i < n {
+            self.first.stop(i)
+        } else {
+            self.second.stop(i - n)
+        };
+
+        stop.offset = Some(self.offset(i));
+        stop
+    }
+
+    fn offset(&self, i: usize) -> f32 {
+        // the palettes are placed as if they were a single list
+        let n = self.first.len();
+        let parts = (self.len() - 1).max(1) as f32;
+
+        if i < n {
+            let end = (n - 1) as f32 / parts;
+            self.first.offset(i) * end
+        } else {
+            let start = n as f32 / parts;
+            start + self.second.offset(i - n) * (1.0 - start)
+        }
+    }
+}
+
+/// A palette with shifted colors.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Rotate<P> {
+    palette: P,
+    n: usize,
+}
+
+impl<P: Palette> Palette for Rotate<P> {
+    fn len(&self) -> usize {
+        self.palette.len()
+    }
+
+    fn stop(&self, i: usize) -> ColorStop {
+        let len = self.palette.len();
+        let j = (i + self.n % len) % len;
+
+        let mut stop = self.palette.stop(j);
+        stop.offset = self.palette.stop(i).offset;
+        stop
+    }
+
+    fn offset(&self, i: usize) -> f32 {
+        self.palette.offset(i)
+    }
+}
+
+/// A palette repeated a few times.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Repeat<P> {
+    palette: P,
+    n: usize,
+}
+
+impl<P: Palette> Palette for Repeat<P> {
+    fn len(&self) -> usize {
+        self.palette.len().saturating_mul(self.n)
+    }
+
+    fn stop(&self, i: usize) -> ColorStop {
+        let m = self.palette.len();
+        let mut stop = self.palette.stop(i % m);
+        stop.offset = Some(self.offset(i));
+        stop
+    }
+
+    fn offset(&self, i: usize) -> f32 {
+        let m = self.palette.len();
+        let (k, j) = (i / m, i % m);
+
+        (k as f32 + self.palette.offset(j)) / self.n as f32
+    }
+}
+
+/// A palette with changed colors.
+#[derive(Clone, Copy)]
+pub struct MapColors<P, F> {
+    palette: P,
+    f: F,
+}
+
+impl<P: Palette, F: Fn(RGB) -> RGB> Palette for MapColors<P, F> {
+    fn len(&self) -> usize {
+        self.palette.len()
+    }
+
+    fn stop(&self, i: usize) -> ColorStop {
+        let mut stop = self.palette.stop(i);
+        stop.color = (self.f)(stop.color);
+        stop
+    }
+
+    fn offset(&self, i: usize) -> f32 {
+        self.palette.offset(i)
+    }
+}
+
+impl<P: fmt::Debug, F> fmt::Debug for MapColors<P, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MapColors")
+            .field("palette", &self.palette)
+            .finish_non_exhaustive()
+    }
+}
+
+/// A palette with colors which are readable on a background.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EnsureContrast<P> {
+    palette: P,
+    background: RGB,
+    ratio: f32,
+}
+
+impl<P: Palette> Palette for EnsureContrast<P> {
+    fn len(&self) -> usize {
+        match self.palette.len() {
+            0 => 0,
+            n => (n - 1) * CONTRAST_SPLITS + 1,
+        }
+    }
+
+    fn stop(&self, i: usize) -> ColorStop {
+        let (j, k) = (i / CONTRAST_SPLITS, i % CONTRAST_SPLITS);
+
+        let mut stop = if k == 0 {
+            self.palette.stop(j)
+        } else {
+            let rgba = |stop: ColorStop| {
+                let c = stop.color;
+                RGBA::new(c.r, c.g, c.b, stop.alpha).convert::<f32>()
+            };
+
+            let interpolation = Interpolation::new();
+            let mix = k as f32 / CONTRAST_SPLITS as f32;
+            let c1 = rgba(self.palette.stop(j));
+            let c2 = rgba(self.palette.stop(j + 1));
+            let c = interpolation.mix_alpha(c1, c2, mix);
+
+            ColorStop::from(interpolation.quantize_alpha::<u8>(c))
+        };
+
+        // all colors are on the same side, so they don't cross the background
+        let darker = is_darker_side(self.background);
+        let ratio = self.ratio * CONTRAST_MARGIN;
+        stop.color = stop
+            .color
+            .ensure_contrast_on(self.background, ratio, darker);
+        stop.offset = Some(self.offset(i));
+        stop
+    }
+
+    fn offset(&self, i: usize) -> f32 {
+        let (j, k) = (i / CONTRAST_SPLITS, i % CONTRAST_SPLITS);
+        if k == 0 {
+            return self.palette.offset(j);
+        }
+
+        let mix = k as f32 / CONTRAST_SPLITS as f32;
+        let (from, to) = (self.palette.offset(j), self.palette.offset(j + 1));
+        from + (to - from) * mix
+    }
+}
+
+// A number of parts each pair of stops is split into by [EnsureContrast],
+// so colors between stops are adjusted as well.
+const CONTRAST_SPLITS: usize = 8;
+
+// A ratio is raised a bit, as colors are still interpolated between the parts.
+const CONTRAST_MARGIN: f32 = 1.1;
+
+// Implements [IntoIterator] over stops of palettes,
+// so they can be passed to [Stops] and [GradientStr](crate::GradientStr) directly.
+macro_rules! impl_into_iterator {
+    ($([$($generics:tt)*] $palette:ty),* $(,)?) => {
+        $(
+            impl<$($generics)*> IntoIterator for $palette {
+                type Item = $crate::stops::ColorStop;
+                type IntoIter = $crate::palette::PaletteIter<Self>;
+
+                fn into_iter(self) -> Self::IntoIter {
+                    $crate::palette::PaletteIter::new(self)
+                }
+            }
+        )*
+    };
+}
+
+pub(crate) use impl_into_iterator;
+
+impl_into_iterator! {
+    [P: Palette] Reverse<P>,
+    [P: Palette] Slice<P>,
+    [P1: Palette, P2: Palette] Concat<P1, P2>,
+    [P: Palette] Rotate<P>,
+    [P: Palette] Repeat<P>,
+    [P: Palette, F: Fn(RGB) -> RGB] MapColors<P, F>,
+    [P: Palette] EnsureContrast<P>,
+}
+
+/// An iterator over stops of a [Palette].
+#[derive(Debug, Clone)]
+pub struct PaletteIter<P> {
+    palette: P,
+    i: usize,
+    end: usize,
+}
+
+impl<P: Palette> PaletteIter<P> {
+    /// Creates an iterator over all stops of a palette.
+    pub fn new(palette: P) -> Self {
+        let end = palette.len();
+        Self { palette, i: 0, end }
+    }
+}
+
+impl<P: Palette> Iterator for PaletteIter<P> {
+    type Item = ColorStop;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.i == self.end {
+            return None;
+        }
+
+        let stop = self.palette.stop(self.i);
+
+        self.i += 1;
+
+        Some(stop)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.end - self.i;
+        (len, Some(len))
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.i = self.i.saturating_add(n).min(self.end);
+        self.next()
+    }
+}
+
+impl<P: Palette> DoubleEndedIterator for PaletteIter<P> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.i == self.end {
+            return None;
+        }
+
+        self.end -= 1;
+
+        Some(self.palette.stop(self.end))
+    }
+}
+
+impl<P: Palette> ExactSizeIterator for PaletteIter<P> {}
+
+impl<P: Palette> FusedIterator for PaletteIter<P> {}
+
+#[cfg(test)]
+mod tests {
+    use super::{ColorStop, Palette, PaletteIter, Positions, Stops};
+    use crate::{
+        interpolation::{ColorSpace, HueInterpolation},
+        Gradient, RGB,
+    };
+
+    const RED: RGB = RGB::new(255, 0, 0);
+    const GREEN: RGB = RGB::new(0, 255, 0);
+    const BLUE: RGB = RGB::new(0, 0, 255);
+
+    #[test]
+    fn reverse_test() {
+        test_palette(
+            [
+                ColorStop::new(RED, 0.0),
+                ColorStop::from(GREEN),
+                ColorStop::new(BLUE, 0.2),
+            ]
+            .reversed(),
+            &[(BLUE, 0.8), (GREEN, 0.9), (RED, 1.0)],
+        );
+    }
+
+    #[test]
+    fn slice_test() {
+        let palette = Gradient::Rainbow.slice(0.2, 0.8);
+        assert_eq!(palette.len(), 4);
+        assert_eq!(palette.stop(0).color, Gradient::Rainbow.at(0.2));
+        assert_eq!(palette.stop(1).color, RGB::new(226, 166, 29));
+        assert_eq!(palette.stop(3).color, Gradient::Rainbow.at(0.8));
+
+        test_palette(
+            [RED, GREEN, BLUE].slice(0.5, 1.0),
+            &[(GREEN, 0.0), (BLUE, 1.0)],
+        );
+        test_palette(
+            [RED, BLUE].slice(0.5, 0.5),
+            &[(RGB::new(188, 0, 188), 0.0), (RGB::new(188, 0, 188), 1.0)],
+        );
+        test_palette(([] as [RGB; 0]).slice(0.0, 1.0), &[]);
+    }
+
+    #[test]
+    fn concat_test() {
+        test_palette(
+            [RED, GREEN].concat([BLUE, RED]),
+            &[
+                (RED, 0.0),
+                (GREEN, 1.0 / 3.0),
+                (BLUE, 2.0 / 3.0),
+                (RED, 1.0),
+            ],
+        );
+        test_palette([RED].concat([GREEN]), &[(RED, 0.0), (GREEN, 1.0)]);
+        test_palette([RED].concat([] as [RGB; 0]), &[(RED, 0.0)]);
+    }
+
+    #[test]
+    fn rotate_test() {
+        let palette = [RED, GREEN, BLUE].rotate(1);
+        assert!(PaletteIter::new(&palette)
+            .map(|s| s.color)
+            .eq([GREEN, BLUE, RED]));
+        assert!(PaletteIter::new(&palette).all(|s| s.offset.is_none()));
+
+        let colors = |n| PaletteIter::new([RED, GREEN, BLUE].rotate(n)).map(|s| s.color);
+        assert!(colors(3).eq([RED, GREEN, BLUE]));
+        assert!(colors(5).eq([BLUE, RED, GREEN]));
+        assert!(colors(usize::MAX).eq([RED, GREEN, BLUE]));
+        assert_eq!([RED, BLUE].rotate(usize::MAX).stop(1).color, RED);
+        assert_eq!(([] as [RGB; 0]).rotate(usize::MAX).len(), 0);
+    }
+
+    #[test]
+    fn repeat_test() {
+        test_palette(
+            [RED, BLUE].repeat(2),
+            &[(RED, 0.0), (BLUE, 0.5), (RED, 0.5), (BLUE, 1.0)],
+        );
+        test_palette([RED, BLUE].repeat(0), &[]);
+
+        let palette = [RED, BLUE].repeat(usize::MAX);
+        assert_eq!(palette.len(), usize::MAX);
+        assert_eq!(palette.stop(usize::MAX - 2).color, BLUE);
+    }
+
+    #[test]
+    fn offset_test() {
+        let stops = [
+            ColorStop::new(RED, 0.4),
+            ColorStop::from(GREEN),
+            ColorStop::new(BLUE, 0.2),
+        ];
+        let nested = Gradient::Rainbow
+            .slice(0.1, 0.9)
+            .reversed()
+            .repeat(2)
+            .concat(stops)
+            .rotate(1);
+        let palettes: [&dyn Palette; 4] = [&Gradient::Rainbow, &[RED], &stops, &nested];
+
+        for palette in palettes {
+            let positions = Positions::new(PaletteIter::new(palette));
+            for (i, stop) in positions.enumerate() {
+                let expected = stop.offset.unwrap();
+                assert!((palette.offset(i) - expected).abs() < 1e-6);
+            }
+        }
+    }
+
+    #[test]
+    fn ensure_contrast_test() {
+        let white = RGB::new(255, 255, 255);
+        let dark = RGB::new(0x1E, 0x1E, 0x1E);
+
+        let palette = [RED, BLUE].ensure_contrast(white, 4.5);
+        assert_eq!(palette.len(), 9);
+        assert_eq!(palette.stop(4).offset, Some(0.5));
+        assert_eq!(([] as [RGB; 0]).ensure_contrast(white, 4.5).len(), 0);
+
+        let spaces = [ColorSpace::Corrected, ColorSpace::Hsl, ColorSpace::Oklch];
+        for gradient in [Gradient::Rainbow, Gradient::Summer, Gradient::Monsoon] {
+            for background in [white, dark] {
+                for space in spaces {
+                    let stops = Stops::new(gradient.ensure_contrast(background, 4.5)).space(space);
+                    assert!(
+                        stops.colors(201).all(|c| c.contrast(background) >= 4.5),
+                        "{:?} in {:?} on {:?}",
+                        gradient,
+                        space,
+                        background
+                    );
+                }
+            }
+        }
+
+        // a hue direction doesn't matter in rectangular spaces
+        let spaces = [
+            ColorSpace::Srgb,
+            ColorSpace::LinearRgb,
+            ColorSpace::Corrected,
+            ColorSpace::Lab,
+            ColorSpace::Oklab,
+        ];
+        for gradient in [Gradient::Rainbow, Gradient::Instagram] {
+            for background in [white, dark] {
+                for space in spaces {
+                    let stops = Stops::new(gradient.ensure_contrast(background, 4.5))
+                        .space(space)
+                        .hue(HueInterpolation::Longer);
+                    assert!(
+                        stops.colors(201).all(|c| c.contrast(background) >= 4.5),
+                        "{:?} in {:?} on {:?}",
+                        gradient,
+                        space,
+                        background
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn map_colors_test() {
+        let palette = [ColorStop::new(RED, 0.2).alpha(128), ColorStop::from(BLUE)];
+        let palette = palette.map_colors(RGB::invert);
+
+        assert_eq!(palette.len(), 2);
+        assert_eq!(
+            palette.stop(0),
+            ColorStop::new(RGB::new(0, 255, 255), 0.2).alpha(128)
+        );
+        assert_eq!(palette.stop(1), ColorStop::from(RGB::new(255, 255, 0)));
+    }
+
+    #[test]
+    fn dyn_palette_test() {
+        let palettes: [&dyn Palette; 2] = [&Gradient::Cristal, &[RED, BLUE]];
+        for palette in palettes {
+            let colors = PaletteIter::new(palette).map(|stop| stop.color);
+            let reversed = PaletteIter::new(palette.reversed()).map(|stop| stop.color);
+            assert!(colors.eq(reversed.rev()));
+
+            assert_eq!(Stops::new(PaletteIter::new(palette)).colors(3).len(), 3);
+        }
+    }
+
+    #[cfg(all(feature = "alloc", target_has_atomic = "ptr"))]
+    #[test]
+    fn shared_palette_test() {
+        use alloc::{sync::Arc, vec};
+
+        let palette: Arc<dyn Palette + Send + Sync> = Arc::new(vec![RED, BLUE]);
+        let reversed = palette.clone().reversed();
+
+        assert_eq!(reversed.len(), 2);
+        assert_eq!(reversed.stop(0).color, BLUE);
+        assert!(Stops::new(PaletteIter::new(palette))
+            .colors(2)
+            .eq([RED, BLUE]));
+    }
+
+    fn test_palette(palette: impl Palette, expected: &[(RGB, f32)]) {
+        assert_eq!(palette.len(), expected.len());
+
+        for (stop, (color, offset)) in PaletteIter::new(&palette).zip(expected) {
+            let got = stop.offset.unwrap();
+            assert_eq!(stop.color, *color);
+            assert!((got - offset).abs() < 1e-6, "{} != {}", got, offset);
+        }
+    }
+}
@@ -0,0 +1,520 @@
+//! This module contains parsing of CSS colors.
+
+use core::cmp::Ordering;
+
+use crate::{
+    rgb::RGBErrorKind,
+    space::{hsl_to_srgb, hwb_to_srgb, normalize_hue, oklch_to_srgb},
+    RGB, RGBA,
+};
+
+// Parses a hex color, a CSS function or a named color.
+pub(crate) fn parse_color(s: &str) -> Result<RGBA<f32>, RGBErrorKind> {
+    let s = s.trim();
+    if s.is_empty() {
+        return Err(RGBErrorKind::Empty);
+    }
+
+    if !s.is_ascii() {
+        return Err(RGBErrorKind::Invalid);
+    }
+
+    if let Some(digits) = s.strip_prefix('#') {
+        return parse_hex(digits, true);
+    }
+
+    if let Some((name, args)) = s.split_once('(') {
+        return parse_function(name.trim_end(), args);
+    }
+
+    if s.eq_ignore_ascii_case("transparent") {
+        return Ok(RGBA::new(0.0, 0.0, 0.0, 0.0));
+    }
+
+    if let Some(color) = named_color(s) {
+        let c = color.convert();
+        return Ok(RGBA::new(c.r, c.g, c.b, 1.0));
+    }
+
+    // a hex color without '#', a word which looks like hex gets a detailed error
+    let is_hex = s.bytes().all(|b| b.is_ascii_hexdigit());
+    let has_digit = s.bytes().any(|b| b.is_ascii_digit());
+    if s.bytes().all(|b| b.is_ascii_alphanumeric()) && (is_hex || has_digit) {
+        return parse_hex(s, false);
+    }
+
+    Err(RGBErrorKind::UnknownName)
+}
+
+// Parses `RGB`, `RGBA`, `RRGGBB` or `RRGGBBAA` hex digits.
+fn parse_hex(s: &str, short: bool) -> Result<RGBA<f32>, RGBErrorKind> {
+    let digits = match s.len() {
+        3 | 4 if short => 1,
+        6 | 8 => 2,
+        _ => return Err(RGBErrorKind::Size),
+    };
+
+    let mut components = [u8::MAX; 4];
+    for pos in 0..s.len() / digits {
+        let c = &s[pos * digits..(pos + 1) * digits];
+        let value =
+            u8::from_str_radix(c, 16).map_err(|error| RGBErrorKind::Format { pos, error })?;
+
+        components[pos] = if digits == 1 { value * 17 } else { value };
+    }
+
+    let [r, g, b, a] = components;
+    Ok(RGBA::new(r, g, b, a).convert())
+}
+
+// Parses arguments of `rgb()`, `rgba()`, `hsl()`, `hsla()`, `hwb()` or `oklch()`.
+fn parse_function(name: &str, args: &str) -> Result<RGBA<f32>, RGBErrorKind> {
+    let args = args
+        .trim_end()
+        .strip_suffix(')')
+        .ok_or(RGBErrorKind::Parenthesis)?;
+
+    let is = |function: &str| name.eq_ignore_ascii_case(function);
+    let function = if is("rgb") || is("rgba") {
+        Function::Rgb
+    } else if is("hsl") || is("hsla") {
+        Function::Hsl
+    } else if is("hwb") {
+        Function::Hwb
+    } else if is("oklch") {
+        Function::Oklch
+    } else {
+        return Err(RGBErrorKind::UnknownFunction);
+    };
+
+    let (args, count) = split_args(args);
+    if !(3..=4).contains(&count) {
+        return Err(RGBErrorKind::Arguments { found: count });
+    }
+
+    let mut values = [Value::Number(1.0); 4];
+    for (pos, arg) in args.iter().take(count).enumerate() {
+        values[pos] = parse_value(arg, pos)?;
+    }
+
+    let [v1, v2, v3, alpha] = values;
+    let c = match function {
+        Function::Rgb => RGB::new(channel(v1, 0)?, channel(v2, 1)?, channel(v3, 2)?),
+        Function::Hsl => {
+            let (h, s, l) = (hue(v1, 0)?, percentage(v2, 1)?, percentage(v3, 2)?);
+            hsl_to_srgb([h, s, l])
+        }
+        Function::Hwb => {
+            let (h, w, b) = (hue(v1, 0)?, percentage(v2, 1)?, percentage(v3, 2)?);
+            hwb_to_srgb([h, w, b])
+        }
+        Function::Oklch => {
+            let l = match v1 {
+                Value::Number(l) => l,
+                Value::Percent(l) => l / 100.0,
+                Value::Angle(_) => return Err(RGBErrorKind::Unit { pos: 0 }),
+            };
+            let c = match v2 {
+                Value::Number(c) => c,
+                // 100% is 0.4
+                Value::Percent(c) => c * 0.004,
+                Value::Angle(_) => return Err(RGBErrorKind::Unit { pos: 1 }),
+            };
+
+            oklch_to_srgb([l.clamp(0.0, 1.0), c.max(0.0), hue(v3, 2)?])
+        }
+    };
+
+    let alpha = match alpha {
+        Value::Number(a) => a,
+        Value::Percent(a) => a / 100.0,
+        Value::Angle(_) => return Err(RGBErrorKind::Unit { pos: 3 }),
+    };
+
+    let clamp = |v: f32| v.clamp(0.0, 1.0);
+    Ok(RGBA::new(clamp(c.r), clamp(c.g), clamp(c.b), clamp(alpha)))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Function {
+    Rgb,
+    Hsl,
+    Hwb,
+    Oklch,
+}
+
+// Splits either comma separated or space separated arguments,
+// an alpha of the latter goes after '/'.
+//
+// It returns a number of arguments, which may be bigger than the list.
+fn split_args(args: &str) -> ([&str; 4], usize) {
+    let mut list = [""; 4];
+    let mut count = 0;
+    let mut push = |arg| {
+        if let Some(slot) = list.get_mut(count) {
+            *slot = arg;
+        }
+
+        count += 1;
+    };
+
+    if args.contains(',') {
+        args.split(',').map(str::trim).for_each(&mut push);
+    } else {
+        let (color, alpha) = match args.split_once('/') {
+            Some((color, alpha)) => (color, Some(alpha.trim())),
+            None => (args, None),
+        };
+
+        color.split_whitespace().for_each(&mut push);
+
+        if let Some(alpha) = alpha {
+            push(alpha);
+        }
+    }
+
+    (list, count)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum Value {
+    Number(f32),
+    Percent(f32),
+    // in degrees
+    Angle(f32),
+}
+
+pub(crate) fn parse_value(s: &str, pos: usize) -> Result<Value, RGBErrorKind> {
+    // an exponent is skipped so it's not taken for a unit
+    let exponent = number_len(s);
+    let split = s[exponent..]
+        .find(|c: char| c.is_ascii_alphabetic() || c == '%')
+        .map_or(s.len(), |i| exponent + i);
+    let (number, unit) = s.split_at(split);
+
+    let number = number
+        .parse::<f32>()
+        .ok()
+        .filter(|n| n.is_finite())
+        .ok_or(RGBErrorKind::Number { pos })?;
+
+    let is = |u: &str| unit.eq_ignore_ascii_case(u);
+    let value = if unit.is_empty() {
+        Value::Number(number)
+    } else if unit == "%" {
+        Value::Percent(number)
+    } else if is("deg") {
+        Value::Angle(number)
+    } else if is("rad") {
+        Value::Angle(number.to_degrees())
+    } else if is("grad") {
+        Value::Angle(number * 0.9)
+    } else if is("turn") {
+        Value::Angle(number * 360.0)
+    } else {
+        return Err(RGBErrorKind::Unit { pos });
+    };
+
+    Ok(value)
+}
+
+// A length of a number at the beginning of a string, including its exponent.
+fn number_len(s: &str) -> usize {
+    let b = s.as_bytes();
+    let digits = |mut i: usize| {
+        while b.get(i).is_some_and(u8::is_ascii_digit) {
+            i += 1;
+        }
+        i
+    };
+
+    let mut i = match b.first() {
+        Some(b'+' | b'-') => 1,
+        _ => 0,
+    };
+
+    i = digits(i);
+    if b.get(i) == Some(&b'.') {
+        i = digits(i + 1);
+    }
+
+    // `e` may start a unit as well
+    if matches!(b.get(i), Some(b'e' | b'E')) {
+        let j = match b.get(i + 1) {
+            Some(b'+' | b'-') => i + 2,
+            _ => i + 1,
+        };
+
+        if b.get(j).is_some_and(u8::is_ascii_digit) {
+            i = digits(j);
+        }
+    }
+
+    i
+}
+
+// A red, green or blue in [0, 255] range or a percentage.
+fn channel(value: Value, pos: usize) -> Result<f32, RGBErrorKind> {
+    match value {
+        Value::Number(v) => Ok(v / 255.0),
+        Value::Percent(v) => Ok(v / 100.0),
+        Value::Angle(_) => Err(RGBErrorKind::Unit { pos }),
+    }
+}
+
+// A percentage, a plain number is treated as a percentage too.
+fn percentage(value: Value, pos: usize) -> Result<f32, RGBErrorKind> {
+    match value {
+        Value::Number(v) | Value::Percent(v) => Ok((v / 100.0).clamp(0.0, 1.0)),
+        Value::Angle(_) => Err(RGBErrorKind::Unit { pos }),
+    }
+}
+
+fn hue(value: Value, pos: usize) -> Result<f32, RGBErrorKind> {
+    match value {
+        Value::Number(h) | Value::Angle(h) => Ok(normalize_hue(h)),
+        Value::Percent(_) => Err(RGBErrorKind::Unit { pos }),
+    }
+}
+
+// Looks up a CSS named color ignoring a case.
+fn named_color(name: &str) -> Option<RGB> {
+    let i = NAMED_COLORS
+        .binary_search_by(|(color, _)| cmp_ignore_case(color, name))
+        .ok()?;
+
+    Some(NAMED_COLORS[i].1)
+}
+
+fn cmp_ignore_case(lowercase: &str, s: &str) -> Ordering {
+    let s = s.bytes().map(|b| b.to_ascii_lowercase());
+    lowercase.bytes().cmp(s)
+}
+
+// CSS named colors sorted by name.
+const NAMED_COLORS: [(&str, RGB); 148] = [
+    ("aliceblue", RGB::new(0xF0, 0xF8, 0xFF)),
+    ("antiquewhite", RGB::new(0xFA, 0xEB, 0xD7)),
+    ("aqua", RGB::new(0x00, 0xFF, 0xFF)),
+    ("aquamarine", RGB::new(0x7F, 0xFF, 0xD4)),
+    ("azure", RGB::new(0xF0, 0xFF, 0xFF)),
+    ("beige", RGB::new(0xF5, 0xF5, 0xDC)),
+    ("bisque", RGB::new(0xFF, 0xE4, 0xC4)),
+    ("black", RGB::new(0x00, 0x00, 0x00)),
+    ("blanchedalmond", RGB::new(0xFF, 0xEB, 0xCD)),
+    ("blue", RGB::new(0x00, 0x00, 0xFF)),
+    ("blueviolet", RGB::new(0x8A, 0x2B, 0xE2)),
+    ("brown", RGB::new(0xA5, 0x2A, 0x2A)),
+    ("burlywood", RGB::new(0xDE, 0xB8, 0x87)),
+    ("cadetblue", RGB::new(0x5F, 0x9E, 0xA0)),
+    ("chartreuse", RGB::new(0x7F, 0xFF, 0x00)),
+    ("chocolate", RGB::new(0xD2, 0x69, 0x1E)),
+    ("coral", RGB::new(0xFF, 0x7F, 0x50)),
+    ("cornflowerblue", RGB::new(0x64, 0x95, 0xED)),
+    ("cornsilk", RGB::new(0xFF, 0xF8, 0xDC)),
+    ("crimson", RGB::new(0xDC, 0x14, 0x3C)),
+    ("cyan", RGB::new(0x00, 0xFF, 0xFF)),
+    ("darkblue", RGB::new(0x00, 0x00, 0x8B)),
+    ("darkcyan", RGB::new(0x00, 0x8B, 0x8B)),
+    ("darkgoldenrod", RGB::new(0xB8, 0x86, 0x0B)),
+    ("darkgray", RGB::new(0xA9, 0xA9, 0xA9)),
+    ("darkgreen", RGB::new(0x00, 0x64, 0x00)),
+    ("darkgrey", RGB::new(0xA9, 0xA9, 0xA9)),
+    ("darkkhaki", RGB::new(0xBD, 0xB7, 0x6B)),
+    ("darkmagenta", RGB::new(0x8B, 0x00, 0x8B)),
+    ("darkolivegreen", RGB::new(0x55, 0x6B, 0x2F)),
+    ("darkorange", RGB::new(0xFF, 0x8C, 0x00)),
+    ("darkorchid", RGB::new(0x99, 0x32, 0xCC)),
+    ("darkred", RGB::new(0x8B, 0x00, 0x00)),
+    ("darksalmon", RGB::new(0xE9, 0x96, 0x7A)),
+    ("darkseagreen", RGB::new(0x8F, 0xBC, 0x8F)),
+    ("darkslateblue", RGB::new(0x48, 0x3D, 0x8B)),
+    ("darkslategray", RGB::new(0x2F, 0x4F, 0x4F)),
+    ("darkslategrey", RGB::new(0x2F, 0x4F, 0x4F)),
+    ("darkturquoise", RGB::new(0x00, 0xCE, 0xD1)),
+    ("darkviolet", RGB::new(0x94, 0x00, 0xD3)),
+    ("deeppink", RGB::new(0xFF, 0x14, 0x93)),
+    ("deepskyblue", RGB::new(0x00, 0xBF, 0xFF)),
+    ("dimgray", RGB::new(0x69, 0x69, 0x69)),
+    ("dimgrey", RGB::new(0x69, 0x69, 0x69)),
+    ("dodgerblue", RGB::new(0x1E, 0x90, 0xFF)),
+    ("firebrick", RGB::new(0xB2, 0x22, 0x22)),
+    ("floralwhite", RGB::new(0xFF, 0xFA, 0xF0)),
+    ("forestgreen", RGB::new(0x22, 0x8B, 0x22)),
+    ("fuchsia", RGB::new(0xFF, 0x00, 0xFF)),
+    ("gainsboro", RGB::new(0xDC, 0xDC, 0xDC)),
+    ("ghostwhite", RGB::new(0xF8, 0xF8, 0xFF)),
+    ("gold", RGB::new(0xFF, 0xD7, 0x00)),
+    ("goldenrod", RGB::new(0xDA, 0xA5, 0x20)),
+    ("gray", RGB::new(0x80, 0x80, 0x80)),
+    ("green", RGB::new(0x00, 0x80, 0x00)),
+    ("greenyellow", RGB::new(0xAD, 0xFF, 0x2F)),
+    ("grey", RGB::new(0x80, 0x80, 0x80)),
+    ("honeydew", RGB::new(0xF0, 0xFF, 0xF0)),
+    ("hotpink", RGB::new(0xFF, 0x69, 0xB4)),
+    ("indianred", RGB::new(0xCD, 0x5C, 0x5C)),
+    ("indigo", RGB::new(0x4B, 0x00, 0x82)),
+    ("ivory", RGB::new(0xFF, 0xFF, 0xF0)),
+    ("khaki", RGB::new(0xF0, 0xE6, 0x8C)),
+    ("lavender", RGB::new(0xE6, 0xE6, 0xFA)),
+    ("lavenderblush", RGB::new(0xFF, 0xF0, 0xF5)),
+    ("lawngreen", RGB::new(0x7C, 0xFC, 0x00)),
+    ("lemonchiffon", RGB::new(0xFF, 0xFA, 0xCD)),
+    ("lightblue", RGB::new(0xAD, 0xD8, 0xE6)),
+    ("lightcoral", RGB::new(0xF0, 0x80, 0x80)),
+    ("lightcyan", RGB::new(0xE0, 0xFF, 0xFF)),
+    ("lightgoldenrodyellow", RGB::new(0xFA, 0xFA, 0xD2)),
+    ("lightgray", RGB::new(0xD3, 0xD3, 0xD3)),
+    ("lightgreen", RGB::new(0x90, 0xEE, 0x90)),
+    ("lightgrey", RGB::new(0xD3, 0xD3, 0xD3)),
+    ("lightpink", RGB::new(0xFF, 0xB6, 0xC1)),
+    ("lightsalmon", RGB::new(0xFF, 0xA0, 0x7A)),
+    ("lightseagreen", RGB::new(0x20, 0xB2, 0xAA)),
+    ("lightskyblue", RGB::new(0x87, 0xCE, 0xFA)),
+    ("lightslategray", RGB::new(0x77, 0x88, 0x99)),
+    ("lightslategrey", RGB::new(0x77, 0x88, 0x99)),
+    ("lightsteelblue", RGB::new(0xB0, 0xC4, 0xDE)),
+    ("lightyellow", RGB::new(0xFF, 0xFF, 0xE0)),
+    ("lime", RGB::new(0x00, 0xFF, 0x00)),
+    ("limegreen", RGB::new(0x32, 0xCD, 0x32)),
+    ("linen", RGB::new(0xFA, 0xF0, 0xE6)),
+    ("magenta", RGB::new(0xFF, 0x00, 0xFF)),
+    ("maroon", RGB::new(0x80, 0x00, 0x00)),
+    ("mediumaquamarine", RGB::new(0x66, 0xCD, 0xAA)),
+    ("mediumblue", RGB::new(0x00, 0x00, 0xCD)),
+    ("mediumorchid", RGB::new(0xBA, 0x55, 0xD3)),
+    ("mediumpurple", RGB::new(0x93, 0x70, 0xDB)),
+    ("mediumseagreen", RGB::new(0x3C, 0xB3, 0x71)),
+    ("mediumslateblue", RGB::new(0x7B, 0x68, 0xEE)),
+    ("mediumspringgreen", RGB::new(0x00, 0xFA, 0x9A)),
+    ("mediumturquoise", RGB::new(0x48, 0xD1, 0xCC)),
+    ("mediumvioletred", RGB::new(0xC7, 0x15, 0x85)),
+    ("midnightblue", RGB::new(0x19, 0x19, 0x70)),
+    ("mintcream", RGB::new(0xF5, 0xFF, 0xFA)),
+    ("mistyrose", RGB::new(0xFF, 0xE4, 0xE1)),
+    ("moccasin", RGB::new(0xFF, 0xE4, 0xB5)),
+    ("navajowhite", RGB::new(0xFF, 0xDE, 0xAD)),
+    ("navy", RGB::new(0x00, 0x00, 0x80)),
+    ("oldlace", RGB::new(0xFD, 0xF5, 0xE6)),
+    ("olive", RGB::new(0x80, 0x80, 0x00)),
+    ("olivedrab", RGB::new(0x6B, 0x8E, 0x23)),
+    ("orange", RGB::new(0xFF, 0xA5, 0x00)),
+    ("orangered", RGB::new(0xFF, 0x45, 0x00)),
+    ("orchid", RGB::new(0xDA, 0x70, 0xD6)),
+    ("palegoldenrod", RGB::new(0xEE, 0xE8, 0xAA)),
+    ("palegreen", RGB::new(0x98, 0xFB, 0x98)),
+    ("paleturquoise", RGB::new(0xAF, 0xEE, 0xEE)),
+    ("palevioletred", RGB::new(0xDB, 0x70, 0x93)),
+    ("papayawhip", RGB::new(0xFF, 0xEF, 0xD5)),
+    ("peachpuff", RGB::new(0xFF, 0xDA, 0xB9)),
+    ("peru", RGB::new(0xCD, 0x85, 0x3F)),
+    ("pink", RGB::new(0xFF, 0xC0, 0xCB)),
+    ("plum", RGB::new(0xDD, 0xA0, 0xDD)),
+    ("powderblue", RGB::new(0xB0, 0xE0, 0xE6)),
+    ("purple", RGB::new(0x80, 0x00, 0x80)),
+    ("rebeccapurple", RGB::new(0x66, 0x33, 0x99)),
+    ("red", RGB::new(0xFF, 0x00, 0x00)),
+    ("rosybrown", RGB::new(0xBC, 0x8F, 0x8F)),
+    ("royalblue", RGB::new(0x41, 0x69, 0xE1)),
+    ("saddlebrown", RGB::new(0x8B, 0x45, 0x13)),
+    ("salmon", RGB::new(0xFA, 0x80, 0x72)),
+    ("sandybrown", RGB::new(0xF4, 0xA4, 0x60)),
+    ("seagreen", RGB::new(0x2E, 0x8B, 0x57)),
+    ("seashell", RGB::new(0xFF, 0xF5, 0xEE)),
+    ("sienna", RGB::new(0xA0, 0x52, 0x2D)),
+    ("silver", RGB::new(0xC0, 0xC0, 0xC0)),
+    ("skyblue", RGB::new(0x87, 0xCE, 0xEB)),
+    ("slateblue", RGB::new(0x6A, 0x5A, 0xCD)),
+    ("slategray", RGB::new(0x70, 0x80, 0x90)),
+    ("slategrey", RGB::new(0x70, 0x80, 0x90)),
+    ("snow", RGB::new(0xFF, 0xFA, 0xFA)),
+    ("springgreen", RGB::new(0x00, 0xFF, 0x7F)),
+    ("steelblue", RGB::new(0x46, 0x82, 0xB4)),
+    ("tan", RGB::new(0xD2, 0xB4, 0x8C)),
+    ("teal", RGB::new(0x00, 0x80, 0x80)),
+    ("thistle", RGB::new(0xD8, 0xBF, 0xD8)),
+    ("tomato", RGB::new(0xFF, 0x63, 0x47)),
+    ("turquoise", RGB::new(0x40, 0xE0, 0xD0)),
+    ("violet", RGB::new(0xEE, 0x82, 0xEE)),
+    ("wheat", RGB::new(0xF5, 0xDE, 0xB3)),
+    ("white", RGB::new(0xFF, 0xFF, 0xFF)),
+    ("whitesmoke", RGB::new(0xF5, 0xF5, 0xF5)),
+    ("yellow", RGB::new(0xFF, 0xFF, 0x00)),
+    ("yellowgreen", RGB::new(0x9A, 0xCD, 0x32)),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_color, RGBErrorKind, NAMED_COLORS, RGBA};
+
+    #[test]
+    fn hex_test() {
+        test_color("#F00", RGBA::new(255, 0, 0, 255));
+        test_color("#f008", RGBA::new(255, 0, 0, 136));
+        test_color("#FF8000", RGBA::new(255, 128, 0, 255));
+        test_color("FF800080", RGBA::new(255, 128, 0, 128));
+
+        test_error("#FF800", RGBErrorKind::Size);
+        test_error("F00", RGBErrorKind::Size);
+        test_error("fff", RGBErrorKind::Size);
+        assert!(matches!(
+            parse_color("ff00g0"),
+            Err(RGBErrorKind::Format { pos: 2, .. })
+        ));
+        assert!(matches!(
+            parse_color("#FF00GG"),
+            Err(RGBErrorKind::Format { pos: 2, .. })
+        ));
+    }
+
+    #[test]
+    fn function_test() {
+        test_color("rgb(255, 128, 0)", RGBA::new(255, 128, 0, 255));
+        test_color("rgba(255, 128, 0, 0.5)", RGBA::new(255, 128, 0, 128));
+        test_color("RGB(100% 50% 0% / 50%)", RGBA::new(255, 128, 0, 128));
+        test_color("rgb(300 -1 0)", RGBA::new(255, 0, 0, 255));
+        test_color("hsl(120, 100%, 50%)", RGBA::new(0, 255, 0, 255));
+        test_color("hsla(0.5turn 100% 50% / 1)", RGBA::new(0, 255, 255, 255));
+        test_color("hwb(240 0% 0%)", RGBA::new(0, 0, 255, 255));
+        test_color("hwb(0 60% 60%)", RGBA::new(128, 128, 128, 255));
+        test_color("oklch(62.8% 0.2577 29.23)", RGBA::new(255, 0, 0, 255));
+        test_color("oklch(1 0 0)", RGBA::new(255, 255, 255, 255));
+
+        test_error("rgb(1, 2, 3", RGBErrorKind::Parenthesis);
+        test_error("lab(1 2 3)", RGBErrorKind::UnknownFunction);
+        test_error("rgb(1 2)", RGBErrorKind::Arguments { found: 2 });
+        test_error("rgb(1, 2, 3, 4, 5)", RGBErrorKind::Arguments { found: 5 });
+        test_error("rgb(1, x, 3)", RGBErrorKind::Number { pos: 1 });
+        test_error("rgb(1, , 3)", RGBErrorKind::Number { pos: 1 });
+        test_error("rgb(1 2 3px)", RGBErrorKind::Unit { pos: 2 });
+        test_error("hsl(10% 2 3)", RGBErrorKind::Unit { pos: 0 });
+        test_error("rgb(1 2 3em)", RGBErrorKind::Unit { pos: 2 });
+        test_color("rgb(1e2% 5E-1% 2.55e+2)", RGBA::new(255, 1, 255, 255));
+        test_color("hsl(1.2e2deg 1e2% 50%)", RGBA::new(0, 255, 0, 255));
+    }
+
+    #[test]
+    fn named_test() {
+        test_color("rebeccapurple", RGBA::new(0x66, 0x33, 0x99, 255));
+        test_color("  DarkSlateGrey ", RGBA::new(0x2F, 0x4F, 0x4F, 255));
+        test_color("transparent", RGBA::new(0, 0, 0, 0));
+        test_error("bluish", RGBErrorKind::UnknownName);
+        test_error("dark blue", RGBErrorKind::UnknownName);
+        test_error("", RGBErrorKind::Empty);
+        test_error("красный", RGBErrorKind::Invalid);
+
+        assert!(NAMED_COLORS.windows(2).all(|w| w[0].0 < w[1].0));
+        for (name, color) in NAMED_COLORS {
+            let c = parse_color(name).unwrap().convert::<u8>();
+            assert_eq!(c, RGBA::new(color.r, color.g, color.b, 255));
+        }
+    }
+
+    fn test_color(s: &str, expected: RGBA) {
+        assert_eq!(parse_color(s).map(RGBA::convert), Ok(expected), "{}", s);
+    }
+
+    fn test_error(s: &str, expected: RGBErrorKind) {
+        assert_eq!(parse_color(s), Err(expected), "{}", s);
+    }
+}
@@ -0,0 +1,343 @@
+//! A module contains settings of how colors are reduced to a terminal palette.
+
+use core::fmt::{self, Display};
+
+use crate::{Component, RGB};
+
+/// A number of colors a terminal is able to display.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ColorDepth {
+    /// 24-bit colors.
+    #[default]
+    TrueColor,
+    /// 256 colors of xterm palette.
+    Ansi256,
+    /// 16 basic colors.
+    Ansi16,
+}
+
+/// A method of hiding bands which appear when colors are reduced.
+///
+/// ```
+/// use tiny_gradient::{quantize::{ColorDepth, Dithering}, Gradient, GradientStr};
+///
+/// let colored = "Hello World!"
+///     .gradient(Gradient::Pastel)
+///     .depth(ColorDepth::Ansi256)
+///     .dithering(Dithering::Ordered);
+///
+/// println!("{}", colored);
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Dithering {
+    /// Colors are rounded to the nearest ones.
+    #[default]
+    None,
+    /// Ordered dithering with a 4x4 Bayer matrix.
+    Ordered,
+    /// Error diffusion along a line.
+    ///
+    /// A rounding error of a cell is carried over to the next one.
+    Diffusion,
+}
+
+/// A part of a cell which is colored.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Target {
+    /// Text.
+    #[default]
+    Foreground,
+    /// A cell behind text.
+    Background,
+}
+
+impl Target {
+    // A code of 256 colors and truecolor sequences.
+    fn code(self) -> usize {
+        match self {
+            Target::Foreground => 38,
+            Target::Background => 48,
+        }
+    }
+}
+
+/// An ANSI escape sequence which sets a color.
+///
+/// It's not followed by a reset sequence `\x1b[0m`.
+///
+/// ```
+/// use tiny_gradient::{quantize::{ColorDepth, Target}, RGB};
+///
+/// let red = RGB::new(255, 0, 0);
+///
+/// assert_eq!(red.ansi(Target::Foreground, ColorDepth::TrueColor).to_string(), "\x1b[38;2;255;0;0m");
+/// assert_eq!(red.ansi(Target::Background, ColorDepth::Ansi256).to_string(), "\x1b[48;5;196m");
+/// assert_eq!(red.ansi(Target::Foreground, ColorDepth::Ansi16).to_string(), "\x1b[91m");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AnsiEscape {
+    color: TermColor,
+    target: Target,
+}
+
+impl AnsiEscape {
+    pub(crate) const fn new(color: TermColor, target: Target) -> Self {
+        Self { color, target }
+    }
+}
+
+impl Display for AnsiEscape {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let code = self.target.code();
+        match self.color {
+            TermColor::Rgb(RGB { r, g, b }) => {
+                f.write_fmt(format_args!("\x1b[{};2;{};{};{}m", code, r, g, b))
+            }
+            TermColor::Ansi256(i) => f.write_fmt(format_args!("\x1b[{};5;{}m", code, i)),
+            TermColor::Ansi16(i) => {
+                // 30..37 and 90..97 for foreground, 40..47 and 100..107 for background
+                let base = code - 8;
+                let code = if i < 8 {
+                    base + i as usize
+                } else {
+                    base + 60 + (i - 8) as usize
+                };
+                f.write_fmt(format_args!("\x1b[{}m", code))
+            }
+        }
+    }
+}
+
+/// A color which can be displayed by a terminal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum TermColor {
+    Rgb(RGB),
+    Ansi256(u8),
+    Ansi16(u8),
+}
+
+/// Reduces colors of cells to a given color depth.
+#[derive(Debug, Clone)]
+pub(crate) struct Quantizer {
+    depth: ColorDepth,
+    dithering: Dithering,
+    error: [f32; 3],
+}
+
+impl Quantizer {
+    pub(crate) const fn new(depth: ColorDepth, dithering: Dithering) -> Self {
+        Self {
+            depth,
+            dithering,
+            error: [0.0; 3],
+        }
+    }
+
+    // Colors are printed as they are.
+    pub(crate) fn is_exact(&self) -> bool {
+        self.depth == ColorDepth::TrueColor && self.dithering == Dithering::None
+    }
+
+    // Starts a new line, an error is not carried over lines.
+    pub(crate) fn reset(&mut self) {
+        self.error = [0.0; 3];
+    }
+
+    // Reduces a normalized color of a cell at a given position.
+    pub(crate) fn quantize(&mut self, c: RGB<f32>, x: usize, y: usize) -> TermColor {
+        let mut c = [c.r, c.g, c.b];
+
+        match self.dithering {
+            Dithering::None => {}
+            Dithering::Ordered => {
+                let threshold = (BAYER[y % 4][x % 4] as f32 + 0.5) / 16.0 - 0.5;
+                for v in &mut c {
+                    *v += threshold * self.depth.spread();
+                }
+            }
+            Dithering::Diffusion => {
+                for (v, error) in c.iter_mut().zip(self.error) {
+                    *v += error;
+                }
+            }
+        }
+
+        let (color, rgb) = self.depth.nearest(c);
+
+        if self.dithering == Dithering::Diffusion {
+            let rgb = [rgb.r, rgb.g, rgb.b];
+            for i in 0..3 {
+                self.error[i] = c[i] - rgb[i].to_normalized();
+            }
+        }
+
+        color
+    }
+}
+
+impl ColorDepth {
+    // An approximate distance between neighbor colors of a palette.
+    fn spread(self) -> f32 {
+        match self {
+            ColorDepth::TrueColor => 1.0 / 255.0,
+            ColorDepth::Ansi256 => 51.0 / 255.0,
+            ColorDepth::Ansi16 => 0.5,
+        }
+    }
+
+    // Finds the nearest color of a palette.
+    fn nearest(self, c: [f32; 3]) -> (TermColor, RGB) {
+        let [r, g, b] = c.map(u8::from_normalized);
+        let rgb = RGB::new(r, g, b);
+
+        match self {
+            ColorDepth::TrueColor => (TermColor::Rgb(rgb), rgb),
+            ColorDepth::Ansi256 => {
+                let cube = rgb_cube_index(rgb);
+                let gray = gray_index(rgb);
+
+                if distance(rgb, ansi256(cube)) <= distance(rgb, ansi256(gray)) {
+                    (TermColor::Ansi256(cube), ansi256(cube))
+                } else {
+                    (TermColor::Ansi256(gray), ansi256(gray))
+                }
+            }
+            ColorDepth::Ansi16 => {
+                let (i, color) = ANSI16
+                    .iter()
+                    .enumerate()
+                    .min_by_key(|(_, &color)| distance(rgb, color))
+                    .expect("the palette is not empty");
+
+                (TermColor::Ansi16(i as u8), *color)
+            }
+        }
+    }
+}
+
+// Returns a color of the 256 colors palette,
+// system colors are taken from xterm defaults.
+fn ansi256(i: u8) -> RGB {
+    match i {
+        16..=231 => {
+            let i = i - 16;
+            RGB::new(
+                CUBE[i as usize / 36],
+                CUBE[i as usize / 6 % 6],
+                CUBE[i as usize % 6],
+            )
+        }
+        232.. => {
+            let v = 8 + (i - 232) * 10;
+            RGB::new(v, v, v)
+        }
+        _ => ANSI16[i as usize],
+    }
+}
+
+fn rgb_cube_index(c: RGB) -> u8 {
+    let level = |v: u8| {
+        (0..CUBE.len())
+            .min_by_key(|&i| CUBE[i].abs_diff(v))
+            .expect("the cube is not empty") as u8
+    };
+
+    16 + 36 * level(c.r) + 6 * level(c.g) + level(c.b)
+}
+
+fn gray_index(c: RGB) -> u8 {
+    let average = (c.r as u16 + c.g as u16 + c.b as u16) / 3;
+    let step = (average.saturating_sub(3) / 10).min(23);
+    232 + step as u8
+}
+
+fn distance(c1: RGB, c2: RGB) -> u32 {
+    let d = |a: u8, b: u8| a.abs_diff(b) as u32 * a.abs_diff(b) as u32;
+    d(c1.r, c2.r) + d(c1.g, c2.g) + d(c1.b, c2.b)
+}
+
+const BAYER: [[u8; 4]; 4] = [[0, 8, 2, 10], [12, 4, 14, 6], [3, 11, 1, 9], [15, 7, 13, 5]];
+
+const CUBE: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+// xterm default colors
+const ANSI16: [RGB; 16] = [
+    RGB::new(0, 0, 0),
+    RGB::new(205, 0, 0),
+    RGB::new(0, 205, 0),
+    RGB::new(205, 205, 0),
+    RGB::new(0, 0, 238),
+    RGB::new(205, 0, 205),
+    RGB::new(0, 205, 205),
+    RGB::new(229, 229, 229),
+    RGB::new(127, 127, 127),
+    RGB::new(255, 0, 0),
+    RGB::new(0, 255, 0),
+    RGB::new(255, 255, 0),
+    RGB::new(92, 92, 255),
+    RGB::new(255, 0, 255),
+    RGB::new(0, 255, 255),
+    RGB::new(255, 255, 255),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::{ansi256, ColorDepth, Dithering, Quantizer, TermColor, RGB};
+
+    #[test]
+    fn nearest_test() {
+        let nearest = |depth: ColorDepth, c: RGB| {
+            let c = c.convert::<f32>();
+            depth.nearest([c.r, c.g, c.b]).0
+        };
+
+        assert_eq!(
+            nearest(ColorDepth::TrueColor, RGB::new(1, 2, 3)),
+            TermColor::Rgb(RGB::new(1, 2, 3))
+        );
+        assert_eq!(
+            nearest(ColorDepth::Ansi256, RGB::new(255, 0, 0)),
+            TermColor::Ansi256(196)
+        );
+        assert_eq!(
+            nearest(ColorDepth::Ansi256, RGB::new(100, 100, 100)),
+            TermColor::Ansi256(241)
+        );
+        assert_eq!(
+            nearest(ColorDepth::Ansi16, RGB::new(250, 10, 10)),
+            TermColor::Ansi16(9)
+        );
+
+        for i in 16..=255 {
+            assert_eq!(
+                nearest(ColorDepth::Ansi256, ansi256(i)),
+                TermColor::Ansi256(i)
+            );
+        }
+    }
+
+    #[test]
+    fn dithering_test() {
+        // a gray which is between two palette colors
+        let gray = RGB::new(0.2, 0.2, 0.2);
+
+        for dithering in [Dithering::Ordered, Dithering::Diffusion] {
+            let mut quantizer = Quantizer::new(ColorDepth::Ansi16, dithering);
+            let mut colors = [0; 2];
+            for x in 0..16 {
+                match quantizer.quantize(gray, x, 0) {
+                    TermColor::Ansi16(0) => colors[0] += 1,
+                    TermColor::Ansi16(8) => colors[1] += 1,
+                    c => panic!("{:?}", c),
+                }
+            }
+
+            assert!(colors[0] > 0 && colors[1] > 0, "{:?}", dithering);
+        }
+
+        let mut quantizer = Quantizer::new(ColorDepth::Ansi16, Dithering::None);
+        for x in 0..16 {
+            assert_eq!(quantizer.quantize(gray, x, 0), TermColor::Ansi16(0));
+        }
+    }
+}
@@ -0,0 +1,101 @@
+//! This module contains a small PRNG and random palettes built with it.
+
+use crate::{
+    space::{normalize_hue, oklch_in_gamut},
+    RGB,
+};
+
+/// SplitMix64 generator.
+#[derive(Debug, Clone)]
+pub(crate) struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub(crate) const fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub(crate) fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    // Returns a value in [min, max) range.
+    pub(crate) fn range(&mut self, min: f32, max: f32) -> f32 {
+        // 24 bits is a precision of f32
+        let unit = (self.next_u64() >> 40) as f32 / (1u64 << 24) as f32;
+        min + (max - min) * unit
+    }
+}
+
+// FNV-1a hash, it's stable across platforms.
+pub(crate) fn hash(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xCBF2_9CE4_8422_2325, |hash, &b| {
+        (hash ^ b as u64).wrapping_mul(0x0000_0100_0000_01B3)
+    })
+}
+
+// Builds a palette going from darker to lighter colors
+// with a limited hue spread and a moderate chroma in OKLCH.
+pub(crate) fn palette(rng: &mut Rng) -> [RGB; 3] {
+    let hue = rng.range(0.0, 360.0);
+    let spread = rng.range(60.0, 150.0) / 2.0;
+    let direction = if rng.next_u64() & 1 == 0 { 1.0 } else { -1.0 };
+
+    let lightness = [
+        rng.range(0.50, 0.62),
+        rng.range(0.64, 0.74),
+        rng.range(0.76, 0.88),
+    ];
+
+    let mut i = 0.0;
+    lightness.map(|l| {
+        let h = normalize_hue(hue + direction * spread * i);
+        let chroma = rng.range(0.09, 0.16);
+        i += 1.0;
+
+        oklch_in_gamut([l, chroma, h]).convert()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{hash, palette, Rng};
+    use crate::{gradient::normalize_rgb, space::srgb_to_oklch};
+
+    #[test]
+    fn rng_test() {
+        let mut rng = Rng::new(0);
+        assert_eq!(rng.next_u64(), 0xE220_A839_7B1D_CDAF);
+
+        let mut rng = Rng::new(1);
+        for _ in 0..1000 {
+            let v = rng.range(2.0, 3.0);
+            assert!((2.0..3.0).contains(&v));
+        }
+
+        assert_eq!(hash(b""), 0xCBF2_9CE4_8422_2325);
+        assert_eq!(hash(b"a"), 0xAF63_DC4C_8601_EC8C);
+    }
+
+    #[test]
+    fn palette_test() {
+        for seed in 0..100 {
+            let colors = palette(&mut Rng::new(seed));
+            assert_eq!(colors, palette(&mut Rng::new(seed)));
+
+            let mut prev = 0.0;
+            for c in colors {
+                let [l, c, _] = srgb_to_oklch(normalize_rgb(c));
+                assert!(l > prev && (0.49..0.89).contains(&l), "{}", l);
+                assert!(c > 0.03, "{}", c);
+                prev = l;
+            }
+        }
+    }
+}
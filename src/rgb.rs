@@ -3,6 +3,14 @@
 use core::fmt::{self, Display};
 use core::{num::ParseIntError, str};
 
+use libm::roundf;
+
+use crate::{
+    css::CssColor,
+    parse::parse_color,
+    quantize::{AnsiEscape, ColorDepth, Dithering, Quantizer, Target},
+};
+
 /// Red Green Blue
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 pub struct RGB<T = u8> {
@@ -21,6 +29,133 @@ impl<T> RGB<T> {
     }
 }
 
+impl<T: Component> RGB<T> {
+    /// Converts components into a different type.
+    ///
+    /// Values are rounded to the nearest one if precision is lost.
+    ///
+    /// ```
+    /// use tiny_gradient::RGB;
+    ///
+    /// let color = RGB::new(0.5_f32, 1.0, 0.0);
+    ///
+    /// assert_eq!(color.convert::<u8>(), RGB::new(128, 255, 0));
+    /// assert_eq!(color.convert::<u16>(), RGB::new(32768, 65535, 0));
+    /// ```
+    pub fn convert<U: Component>(self) -> RGB<U> {
+        RGB::new(
+            U::from_normalized(self.r.to_normalized()),
+            U::from_normalized(self.g.to_normalized()),
+            U::from_normalized(self.b.to_normalized()),
+        )
+    }
+}
+
+impl RGB {
+    /// Returns a CSS `rgb()` representation.
+    ///
+    /// ```
+    /// use tiny_gradient::RGB;
+    ///
+    /// assert_eq!(RGB::new(255, 128, 0).css().to_string(), "rgb(255 128 0)");
+    /// ```
+    pub const fn css(self) -> CssColor<Self> {
+        CssColor::new(self)
+    }
+
+    /// Returns an ANSI escape sequence which sets the color,
+    /// it's reduced to the nearest one of a given color depth.
+    ///
+    /// See [AnsiEscape].
+    pub fn ansi(self, target: Target, depth: ColorDepth) -> AnsiEscape {
+        let mut quantizer = Quantizer::new(depth, Dithering::None);
+        let color = quantizer.quantize(self.convert(), 0, 0);
+        AnsiEscape::new(color, target)
+    }
+}
+
+/// A type of [RGB] components.
+///
+/// Integers take their whole range, while [f32] is in [0, 1] range.
+pub trait Component: Copy {
+    /// Converts a value from [0, 1] range rounding it to the nearest one.
+    fn from_normalized(c: f32) -> Self;
+    /// Converts a value from [0, 1] range dropping its fractional part.
+    ///
+    /// [f32] is kept as it is.
+    fn truncate_normalized(c: f32) -> Self;
+    /// Converts a value into [0, 1] range.
+    fn to_normalized(self) -> f32;
+}
+
+impl Component for u8 {
+    fn from_normalized(c: f32) -> Self {
+        roundf(clamp_normalized(c) * 255.0) as u8
+    }
+
+    fn truncate_normalized(c: f32) -> Self {
+        (clamp_normalized(c) * 255.9999) as u8
+    }
+
+    fn to_normalized(self) -> f32 {
+        self as f32 / 255.0
+    }
+}
+
+impl Component for u16 {
+    fn from_normalized(c: f32) -> Self {
+        roundf(clamp_normalized(c) * 65535.0) as u16
+    }
+
+    fn truncate_normalized(c: f32) -> Self {
+        (clamp_normalized(c) * 65535.99) as u16
+    }
+
+    fn to_normalized(self) -> f32 {
+        self as f32 / 65535.0
+    }
+}
+
+impl Component for f32 {
+    fn from_normalized(c: f32) -> Self {
+        clamp_normalized(c)
+    }
+
+    fn truncate_normalized(c: f32) -> Self {
+        clamp_normalized(c)
+    }
+
+    fn to_normalized(self) -> f32 {
+        self
+    }
+}
+
+fn clamp_normalized(c: f32) -> f32 {
+    if c.is_nan() {
+        0.0
+    } else {
+        c.clamp(0.0, 1.0)
+    }
+}
+
+impl From<RGB<u8>> for RGB<u16> {
+    fn from(c: RGB<u8>) -> Self {
+        c.convert()
+    }
+}
+
+impl From<RGB<u8>> for RGB<f32> {
+    fn from(c: RGB<u8>) -> Self {
+        c.convert()
+    }
+}
+
+impl From<RGB<u16>> for RGB<f32> {
+    fn from(c: RGB<u16>) -> Self {
+        c.convert()
+    }
+}
+
 impl From<(u8, u8, u8)> for RGB {
     fn from((r, g, b): (u8, u8, u8)) -> Self {
         Self { r, g, b }
@@ -30,50 +165,117 @@ impl From<(u8, u8, u8)> for RGB {
 impl str::FromStr for RGB {
     type Err = ParseRGBError;
 
-    fn from_str(mut s: &str) -> Result<Self, Self::Err> {
-        if !s.is_ascii() {
-            return Err(ParseRGBError::new(RGBErrorKind::Invalid));
+    /// Parses a color in any format supported by [RGBA](crate::RGBA),
+    /// a color must be fully opaque.
+    ///
+    /// ```
+    /// use tiny_gradient::RGB;
+    ///
+    /// assert_eq!("#F80".parse(), Ok(RGB::new(255, 136, 0)));
+    /// assert_eq!("rgb(255 136 0)".parse(), Ok(RGB::new(255, 136, 0)));
+    /// assert_eq!("darkorange".parse(), Ok(RGB::new(255, 140, 0)));
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let c = parse_color(s).map_err(ParseRGBError::new)?.convert::<u8>();
+        if c.a != u8::MAX {
+            return Err(ParseRGBError::new(RGBErrorKind::Translucent));
         }
 
-        if let Some(stripped) = s.strip_prefix('#') {
-            s = stripped;
-        }
+        Ok(c.rgb())
+    }
+}
 
-        if s.len() != 6 {
-            return Err(ParseRGBError::new(RGBErrorKind::Size));
+/// Formats the color as `rrggbb`, the alternate flag adds `#`.
+///
+/// ```
+/// use tiny_gradient::RGB;
+///
+/// let color = RGB::new(255, 128, 0);
+///
+/// assert_eq!(format!("{:#x}", color), "#ff8000");
+/// assert_eq!(format!("{:#x}", color).parse(), Ok(color));
+/// ```
+impl fmt::LowerHex for RGB {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            f.write_str("#")?;
         }
 
-        let r = u8::from_str_radix(&s[..2], 16)
-            .map_err(|e| ParseRGBError::new(RGBErrorKind::Format { error: e, pos: 0 }))?;
-
-        let g = u8::from_str_radix(&s[2..4], 16)
-            .map_err(|e| ParseRGBError::new(RGBErrorKind::Format { error: e, pos: 1 }))?;
+        f.write_fmt(format_args!("{:02x}{:02x}{:02x}", self.r, self.g, self.b))
+    }
+}
 
-        let b = u8::from_str_radix(&s[4..6], 16)
-            .map_err(|e| ParseRGBError::new(RGBErrorKind::Format { error: e, pos: 2 }))?;
+/// Formats the color as `RRGGBB`, the alternate flag adds `#`.
+///
+/// ```
+/// use tiny_gradient::RGB;
+///
+/// assert_eq!(format!("{:X}", RGB::new(255, 128, 0)), "FF8000");
+/// ```
+impl fmt::UpperHex for RGB {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            f.write_str("#")?;
+        }
 
-        Ok(Self { r, g, b })
+        f.write_fmt(format_args!("{:02X}{:02X}{:02X}", self.r, self.g, self.b))
     }
 }
 
+/// An error which can be returned when parsing a color.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ParseRGBError {
     kind: RGBErrorKind,
 }
 
+/// A cause of [ParseRGBError].
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
 pub enum RGBErrorKind {
+    /// A string is empty.
+    Empty,
+    /// A hex color has a wrong number of digits.
     Size,
+    /// A string contains non ASCII symbols.
     Invalid,
-    Format { pos: usize, error: ParseIntError },
+    /// A hex component is not valid.
+    Format {
+        /// An index of a component.
+        pos: usize,
+        /// A cause.
+        error: ParseIntError,
+    },
+    /// A color name is unknown.
+    UnknownName,
+    /// A function is not one of `rgb`, `rgba`, `hsl`, `hsla`, `hwb` or `oklch`.
+    UnknownFunction,
+    /// A function is not closed.
+    Parenthesis,
+    /// A function got a wrong number of arguments.
+    Arguments {
+        /// A number of arguments.
+        found: usize,
+    },
+    /// An argument is not a number.
+    Number {
+        /// An index of an argument.
+        pos: usize,
+    },
+    /// An argument has a unit which is not allowed.
+    Unit {
+        /// An index of an argument.
+        pos: usize,
+    },
+    /// A color has alpha while an opaque one is expected.
+    Translucent,
 }
 
 impl ParseRGBError {
-    fn new(kind: RGBErrorKind) -> Self {
+    pub(crate) fn new(kind: RGBErrorKind) -> Self {
         Self { kind }
     }
 
-    /// Outputs the detailed cause of parsing an integer failing.
+    /// Outputs the detailed cause of parsing a color failing.
     #[must_use]
     pub fn kind(&self) -> &RGBErrorKind {
         &self.kind
@@ -83,16 +285,75 @@ impl ParseRGBError {
 impl Display for ParseRGBError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match &self.kind {
-            RGBErrorKind::Size => {
-                "cannot parse rgb from string with size not equal to 6 (or 7 if '#' is used)".fmt(f)
-            }
+            RGBErrorKind::Empty => "cannot parse color from empty string".fmt(f),
+            RGBErrorKind::Size => "hex color must have 3, 4, 6 or 8 digits".fmt(f),
             RGBErrorKind::Invalid => "invalid string which contains UTF8 symbols".fmt(f),
             RGBErrorKind::Format { pos, error } => {
                 error.fmt(f)?;
                 " on position ".fmt(f)?;
                 pos.fmt(f)
             }
+            RGBErrorKind::UnknownName => "unknown color name".fmt(f),
+            RGBErrorKind::UnknownFunction => "unknown color function".fmt(f),
+            RGBErrorKind::Parenthesis => "missing closing parenthesis".fmt(f),
+            RGBErrorKind::Arguments { found } => f.write_fmt(format_args!(
+                "expected 3 or 4 arguments but found {}",
+                found
+            )),
+            RGBErrorKind::Number { pos } => {
+                f.write_fmt(format_args!("invalid number on position {}", pos))
+            }
+            RGBErrorKind::Unit { pos } => {
+                f.write_fmt(format_args!("unexpected unit on position {}", pos))
+            }
+            RGBErrorKind::Translucent => "expected an opaque color".fmt(f),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{RGBErrorKind, RGB};
+
+    #[test]
+    fn convert_test() {
+        for c in 0..=255 {
+            let rgb = RGB::new(c, c, c);
+            assert_eq!(
+                RGB::<u16>::from(rgb),
+                RGB::new(c as u16 * 257, c as u16 * 257, c as u16 * 257)
+            );
+            assert_eq!(RGB::<u16>::from(rgb).convert::<u8>(), rgb);
+            assert_eq!(RGB::<f32>::from(rgb).convert::<u8>(), rgb);
         }
+
+        assert_eq!(
+            RGB::new(2.0, -1.0, f32::NAN).convert::<u8>(),
+            RGB::new(255, 0, 0)
+        );
+        assert_eq!(
+            RGB::<f32>::from(RGB::new(0_u16, 65535, 0)),
+            RGB::new(0.0, 1.0, 0.0)
+        );
+    }
+
+    #[test]
+    fn parse_test() {
+        assert_eq!("#FF0080".parse(), Ok(RGB::new(255, 0, 128)));
+        assert_eq!("ff0080".parse(), Ok(RGB::new(255, 0, 128)));
+        assert_eq!("#fff".parse(), Ok(RGB::new(255, 255, 255)));
+        assert_eq!("hsl(0 100% 50% / 1)".parse(), Ok(RGB::new(255, 0, 0)));
+
+        let kind = |s: &str| s.parse::<RGB>().unwrap_err().kind().clone();
+        assert_eq!(kind("#FF000080"), RGBErrorKind::Translucent);
+        assert_eq!(kind("transparent"), RGBErrorKind::Translucent);
+        assert_eq!(kind("#FF00"), RGBErrorKind::Translucent);
+        assert_eq!(kind("#FF000"), RGBErrorKind::Size);
+        assert_eq!(kind("fff"), RGBErrorKind::Size);
+        assert!(matches!(
+            kind("ff00g0"),
+            RGBErrorKind::Format { pos: 2, .. }
+        ));
     }
 }
 
@@ -0,0 +1,175 @@
+//! This module contains [RGBA] structure.
+
+use core::{fmt, str};
+
+use crate::{
+    css::CssColor,
+    parse::parse_color,
+    rgb::{Component, ParseRGBError, RGB},
+};
+
+/// Red Green Blue Alpha
+///
+/// Alpha is an opacity of a color, the max value is fully opaque.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct RGBA<T = u8> {
+    /// Red
+    pub r: T,
+    /// Green
+    pub g: T,
+    /// Blue
+    pub b: T,
+    /// Alpha
+    pub a: T,
+}
+
+impl<T> RGBA<T> {
+    /// Creates a new [RGBA]
+    pub const fn new(r: T, g: T, b: T, a: T) -> Self {
+        Self { r, g, b, a }
+    }
+
+    /// Returns a color without alpha.
+    pub fn rgb(self) -> RGB<T> {
+        RGB::new(self.r, self.g, self.b)
+    }
+}
+
+impl RGBA {
+    /// Returns a CSS `rgb()` representation.
+    ///
+    /// ```
+    /// use tiny_gradient::RGBA;
+    ///
+    /// assert_eq!(RGBA::new(255, 0, 0, 128).css().to_string(), "rgb(255 0 0 / 0.502)");
+    /// ```
+    pub const fn css(self) -> CssColor<Self> {
+        CssColor::new(self)
+    }
+}
+
+impl<T: Component> RGBA<T> {
+    /// Converts components into a different type.
+    ///
+    /// Values are rounded to the nearest one if precision is lost.
+    pub fn convert<U: Component>(self) -> RGBA<U> {
+        let rgb = self.rgb().convert();
+        RGBA::new(
+            rgb.r,
+            rgb.g,
+            rgb.b,
+            U::from_normalized(self.a.to_normalized()),
+        )
+    }
+
+    /// Places the color over a backdrop and returns a visible color.
+    ///
+    /// ```
+    /// use tiny_gradient::{RGB, RGBA};
+    ///
+    /// let color = RGBA::new(255_u8, 0, 0, 128);
+    ///
+    /// assert_eq!(color.composite(RGB::new(0, 0, 255)), RGB::new(128, 0, 127));
+    /// ```
+    pub fn composite(self, backdrop: RGB<T>) -> RGB<T> {
+        let c = composite(self.convert(), backdrop.convert());
+        c.convert()
+    }
+}
+
+impl From<RGB> for RGBA {
+    fn from(RGB { r, g, b }: RGB) -> Self {
+        Self::new(r, g, b, u8::MAX)
+    }
+}
+
+impl From<(u8, u8, u8, u8)> for RGBA {
+    fn from((r, g, b, a): (u8, u8, u8, u8)) -> Self {
+        Self::new(r, g, b, a)
+    }
+}
+
+impl str::FromStr for RGBA {
+    type Err = ParseRGBError;
+
+    /// Parses a color in one of the formats.
+    ///
+    /// - `#RGB`, `#RGBA`, `#RRGGBB` or `#RRGGBBAA` (`#` may be omitted for the longer ones).
+    /// - `rgb()`, `rgba()`, `hsl()`, `hsla()`, `hwb()` or `oklch()` CSS functions.
+    /// - CSS named colors including `transparent`.
+    ///
+    /// ```
+    /// use tiny_gradient::RGBA;
+    ///
+    /// assert_eq!("#F008".parse(), Ok(RGBA::new(255, 0, 0, 136)));
+    /// assert_eq!("rgb(255 0 0 / 50%)".parse(), Ok(RGBA::new(255, 0, 0, 128)));
+    /// assert_eq!("hwb(0 0% 0%)".parse(), Ok(RGBA::new(255, 0, 0, 255)));
+    /// assert_eq!("Red".parse(), Ok(RGBA::new(255, 0, 0, 255)));
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let c = parse_color(s).map_err(ParseRGBError::new)?;
+        Ok(c.convert())
+    }
+}
+
+/// Formats the color as `rrggbbaa`, the alternate flag adds `#`.
+///
+/// ```
+/// use tiny_gradient::RGBA;
+///
+/// let color = RGBA::new(255, 0, 10, 128);
+///
+/// assert_eq!(format!("{:#x}", color), "#ff000a80");
+/// assert_eq!(format!("{:x}", color).parse(), Ok(color));
+/// ```
+impl fmt::LowerHex for RGBA {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::LowerHex::fmt(&self.rgb(), f)?;
+        f.write_fmt(format_args!("{:02x}", self.a))
+    }
+}
+
+/// Formats the color as `RRGGBBAA`, the alternate flag adds `#`.
+impl fmt::UpperHex for RGBA {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::UpperHex::fmt(&self.rgb(), f)?;
+        f.write_fmt(format_args!("{:02X}", self.a))
+    }
+}
+
+// Source over compositing of normalized colors.
+pub(crate) fn composite(c: RGBA<f32>, backdrop: RGB<f32>) -> RGB<f32> {
+    let blend = |v: f32, b: f32| v * c.a + b * (1.0 - c.a);
+    RGB::new(
+        blend(c.r, backdrop.r),
+        blend(c.g, backdrop.g),
+        blend(c.b, backdrop.b),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{RGB, RGBA};
+
+    #[test]
+    fn parse_test() {
+        assert_eq!("#FF000080".parse(), Ok(RGBA::new(255, 0, 0, 128)));
+        assert_eq!("00ff00ff".parse(), Ok(RGBA::new(0, 255, 0, 255)));
+        assert_eq!("#FF0000".parse(), Ok(RGBA::new(255, 0, 0, 255)));
+        assert!("#FF0000GG".parse::<RGBA>().is_err());
+    }
+
+    #[test]
+    fn composite_test() {
+        let backdrop = RGB::new(10, 20, 30);
+        assert_eq!(
+            RGBA::new(255_u8, 0, 0, 255).composite(backdrop),
+            RGB::new(255, 0, 0)
+        );
+        assert_eq!(RGBA::new(255_u8, 0, 0, 0).composite(backdrop), backdrop);
+        assert_eq!(
+            RGBA::new(1.0, 1.0, 1.0, 0.5).composite(RGB::new(0.0, 0.0, 0.0)),
+            RGB::new(0.5, 0.5, 0.5)
+        );
+    }
+}
@@ -0,0 +1,366 @@
+//! Conversions of normalized sRGB colors into other color spaces and back.
+//!
+//! Cylindrical spaces keep a hue in degrees [0, 360),
+//! a hue of an achromatic color is [f32::NAN].
+//!
+//! The matrices are taken from CSS Color Module Level 4.
+
+use libm::{atan2f, cbrtf, cosf, fabsf, fmodf, hypotf, sinf};
+
+use crate::{
+    gradient::{srgb_apply_companding_color, srgb_inverse_color},
+    RGB,
+};
+
+const D50_WHITE: [f32; 3] = [0.3457 / 0.3585, 1.0, (1.0 - 0.3457 - 0.3585) / 0.3585];
+
+const LAB_EPSILON: f32 = 216.0 / 24389.0;
+const LAB_KAPPA: f32 = 24389.0 / 27.0;
+
+const LINEAR_SRGB_TO_XYZ_D65: [[f32; 3]; 3] = [
+    [0.412_390_8, 0.357_584_33, 0.180_480_8],
+    [0.212_639, 0.715_168_7, 0.072_192_32],
+    [0.019_330_818, 0.119_194_78, 0.950_532_15],
+];
+
+const XYZ_D65_TO_LINEAR_SRGB: [[f32; 3]; 3] = [
+    [3.240_97, -1.537_383_2, -0.498_610_76],
+    [-0.969_243_65, 1.875_967_5, 0.041_555_06],
+    [0.055_630_08, -0.203_976_96, 1.056_971_5],
+];
+
+const XYZ_D65_TO_D50: [[f32; 3]; 3] = [
+    [1.047_929_8, 0.022_946_794, -0.050_192_23],
+    [0.029_627_815, 0.990_434_5, -0.017_073_825],
+    [-0.009_243_058, 0.015_055_145, 0.751_874_3],
+];
+
+const XYZ_D50_TO_D65: [[f32; 3]; 3] = [
+    [0.955_473_4, -0.023_098_537, 0.063_259_31],
+    [-0.028_369_706, 1.009_995_5, 0.021_041_399],
+    [0.012_314_002, -0.020_507_697, 1.330_366],
+];
+
+const LINEAR_SRGB_TO_LMS: [[f32; 3]; 3] = [
+    [0.412_221_46, 0.536_332_55, 0.051_445_995],
+    [0.211_903_5, 0.680_699_5, 0.107_396_96],
+    [0.088_302_46, 0.281_718_85, 0.629_978_7],
+];
+
+const LMS_TO_LINEAR_SRGB: [[f32; 3]; 3] = [
+    [4.076_741_7, -3.307_711_6, 0.230_969_94],
+    [-1.268_438, 2.609_757_4, -0.341_319_38],
+    [-0.004_196_086_3, -0.703_418_6, 1.707_614_7],
+];
+
+const LMS_TO_OKLAB: [[f32; 3]; 3] = [
+    [0.210_454_26, 0.793_617_8, -0.004_072_047],
+    [1.977_998_5, -2.428_592_2, 0.450_593_7],
+    [0.025_904_037, 0.782_771_77, -0.808_675_77],
+];
+
+const OKLAB_TO_LMS: [[f32; 3]; 3] = [
+    [1.0, 0.396_337_78, 0.215_803_76],
+    [1.0, -0.105_561_346, -0.063_854_17],
+    [1.0, -0.089_484_18, -1.291_485_5],
+];
+
+// Chroma below which a hue is considered to be powerless.
+const LCH_ACHROMATIC: f32 = 0.02;
+const OKLCH_ACHROMATIC: f32 = 0.000_2;
+
+pub(crate) fn srgb_to_linear(c: RGB<f32>) -> [f32; 3] {
+    [
+        srgb_inverse_color(c.r),
+        srgb_inverse_color(c.g),
+        srgb_inverse_color(c.b),
+    ]
+}
+
+pub(crate) fn linear_to_srgb([r, g, b]: [f32; 3]) -> RGB<f32> {
+    RGB::new(
+        srgb_apply_companding_color(r),
+        srgb_apply_companding_color(g),
+        srgb_apply_companding_color(b),
+    )
+}
+
+// A relative luminance, it's Y of CIE XYZ.
+pub(crate) fn luminance(c: RGB<f32>) -> f32 {
+    let [r, g, b] = srgb_to_linear(c);
+    let [_, y, _] = LINEAR_SRGB_TO_XYZ_D65;
+    y[0] * r + y[1] * g + y[2] * b
+}
+
+pub(crate) fn srgb_to_hsl(c: RGB<f32>) -> [f32; 3] {
+    let (max, min, hue) = hue_of(c);
+    let l = (max + min) / 2.0;
+    let d = max - min;
+
+    let s = if d == 0.0 || l == 0.0 || l == 1.0 {
+        0.0
+    } else {
+        d / (1.0 - fabsf(2.0 * l - 1.0))
+    };
+
+    [hue, s, l]
+}
+
+pub(crate) fn hsl_to_srgb([h, s, l]: [f32; 3]) -> RGB<f32> {
+    let h = if h.is_nan() { 0.0 } else { h };
+    let a = s * l.min(1.0 - l);
+    let f = |n: f32| {
+        let k = fmodf(n + h / 30.0, 12.0);
+        l - a * (k - 3.0).min(9.0 - k).clamp(-1.0, 1.0)
+    };
+
+    RGB::new(f(0.0), f(8.0), f(4.0))
+}
+
+pub(crate) fn srgb_to_hwb(c: RGB<f32>) -> [f32; 3] {
+    let (max, min, hue) = hue_of(c);
+
+    [hue, min, 1.0 - max]
+}
+
+pub(crate) fn hwb_to_srgb([h, w, b]: [f32; 3]) -> RGB<f32> {
+    // whiteness and blackness are scaled down if they exceed 1 together
+    if w + b >= 1.0 {
+        let gray = w / (w + b);
+        return RGB::new(gray, gray, gray);
+    }
+
+    let c = hsv_to_srgb([h, 1.0, 1.0]);
+    let f = |v: f32| v * (1.0 - w - b) + w;
+
+    RGB::new(f(c.r), f(c.g), f(c.b))
+}
+
+pub(crate) fn srgb_to_hsv(c: RGB<f32>) -> [f32; 3] {
+    let (max, min, hue) = hue_of(c);
+    let s = if max == 0.0 { 0.0 } else { (max - min) / max };
+
+    [hue, s, max]
+}
+
+pub(crate) fn hsv_to_srgb([h, s, v]: [f32; 3]) -> RGB<f32> {
+    let h = if h.is_nan() { 0.0 } else { h };
+    let f = |n: f32| {
+        let k = fmodf(n + h / 60.0, 6.0);
+        v - v * s * k.min(4.0 - k).clamp(0.0, 1.0)
+    };
+
+    RGB::new(f(5.0), f(3.0), f(1.0))
+}
+
+pub(crate) fn srgb_to_lab(c: RGB<f32>) -> [f32; 3] {
+    let xyz = multiply(&LINEAR_SRGB_TO_XYZ_D65, srgb_to_linear(c));
+    let xyz = multiply(&XYZ_D65_TO_D50, xyz);
+
+    let f = |i: usize| {
+        let v = xyz[i] / D50_WHITE[i];
+        if v > LAB_EPSILON {
+            cbrtf(v)
+        } else {
+            (LAB_KAPPA * v + 16.0) / 116.0
+        }
+    };
+
+    let (fx, fy, fz) = (f(0), f(1), f(2));
+
+    [116.0 * fy - 16.0, 500.0 * (fx - fy), 200.0 * (fy - fz)]
+}
+
+pub(crate) fn lab_to_srgb([l, a, b]: [f32; 3]) -> RGB<f32> {
+    let fy = (l + 16.0) / 116.0;
+    let fx = a / 500.0 + fy;
+    let fz = fy - b / 200.0;
+
+    let f = |v: f32| {
+        let cube = v * v * v;
+        if cube > LAB_EPSILON {
+            cube
+        } else {
+            (116.0 * v - 16.0) / LAB_KAPPA
+        }
+    };
+
+    let y = if l > LAB_KAPPA * LAB_EPSILON {
+        fy * fy * fy
+    } else {
+        l / LAB_KAPPA
+    };
+
+    let xyz = [f(fx) * D50_WHITE[0], y * D50_WHITE[1], f(fz) * D50_WHITE[2]];
+    let xyz = multiply(&XYZ_D50_TO_D65, xyz);
+
+    linear_to_srgb(multiply(&XYZ_D65_TO_LINEAR_SRGB, xyz))
+}
+
+pub(crate) fn srgb_to_lch(c: RGB<f32>) -> [f32; 3] {
+    to_polar(srgb_to_lab(c), LCH_ACHROMATIC)
+}
+
+pub(crate) fn lch_to_srgb(c: [f32; 3]) -> RGB<f32> {
+    lab_to_srgb(from_polar(c))
+}
+
+pub(crate) fn srgb_to_oklab(c: RGB<f32>) -> [f32; 3] {
+    let lms = multiply(&LINEAR_SRGB_TO_LMS, srgb_to_linear(c));
+    let lms = lms.map(cbrtf);
+
+    multiply(&LMS_TO_OKLAB, lms)
+}
+
+pub(crate) fn oklab_to_srgb(c: [f32; 3]) -> RGB<f32> {
+    let lms = multiply(&OKLAB_TO_LMS, c);
+    let lms = lms.map(|v| v * v * v);
+
+    linear_to_srgb(multiply(&LMS_TO_LINEAR_SRGB, lms))
+}
+
+pub(crate) fn srgb_to_oklch(c: RGB<f32>) -> [f32; 3] {
+    to_polar(srgb_to_oklab(c), OKLCH_ACHROMATIC)
+}
+
+pub(crate) fn oklch_to_srgb(c: [f32; 3]) -> RGB<f32> {
+    oklab_to_srgb(from_polar(c))
+}
+
+// Lowers chroma until a color fits into sRGB so its hue is kept.
+pub(crate) fn oklch_in_gamut([l, mut chroma, h]: [f32; 3]) -> RGB<f32> {
+    loop {
+        let c = oklch_to_srgb([l, chroma, h]);
+        let fits = [c.r, c.g, c.b]
+            .iter()
+            .all(|v| (-1e-4..=1.0 + 1e-4).contains(v));
+
+        if fits || chroma <= 0.0 {
+            return c;
+        }
+
+        chroma -= 0.005;
+    }
+}
+
+pub(crate) fn normalize_hue(h: f32) -> f32 {
+    let h = fmodf(h, 360.0);
+    if h < 0.0 {
+        h + 360.0
+    } else {
+        h
+    }
+}
+
+// Returns max and min components and a hue of a color.
+fn hue_of(RGB { r, g, b }: RGB<f32>) -> (f32, f32, f32) {
+    let max = r.max(g).max(b);
+    let min = r.min(g).min(b);
+    let d = max - min;
+
+    let hue = if d == 0.0 {
+        f32::NAN
+    } else if max == r {
+        (g - b) / d + if g < b { 6.0 } else { 0.0 }
+    } else if max == g {
+        (b - r) / d + 2.0
+    } else {
+        (r - g) / d + 4.0
+    };
+
+    (max, min, normalize_hue(hue * 60.0))
+}
+
+fn to_polar([l, a, b]: [f32; 3], achromatic: f32) -> [f32; 3] {
+    let c = hypotf(a, b);
+    let h = if c < achromatic {
+        f32::NAN
+    } else {
+        normalize_hue(atan2f(b, a).to_degrees())
+    };
+
+    [l, c, h]
+}
+
+fn from_polar([l, c, h]: [f32; 3]) -> [f32; 3] {
+    if h.is_nan() {
+        return [l, 0.0, 0.0];
+    }
+
+    let h = h.to_radians();
+
+    [l, c * cosf(h), c * sinf(h)]
+}
+
+pub(crate) fn multiply(m: &[[f32; 3]; 3], [x, y, z]: [f32; 3]) -> [f32; 3] {
+    m.map(|row| row[0] * x + row[1] * y + row[2] * z)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip_test() {
+        let colors = [
+            RGB::new(0.0, 0.0, 0.0),
+            RGB::new(1.0, 1.0, 1.0),
+            RGB::new(0.5, 0.5, 0.5),
+            RGB::new(1.0, 0.0, 0.0),
+            RGB::new(0.2, 0.6, 0.9),
+            RGB::new(0.9, 0.8, 0.1),
+        ];
+
+        type Conversion = (fn(RGB<f32>) -> [f32; 3], fn([f32; 3]) -> RGB<f32>);
+        let conversions: [Conversion; 6] = [
+            (srgb_to_hsl, hsl_to_srgb),
+            (srgb_to_hsv, hsv_to_srgb),
+            (srgb_to_lab, lab_to_srgb),
+            (srgb_to_lch, lch_to_srgb),
+            (srgb_to_oklab, oklab_to_srgb),
+            (srgb_to_oklch, oklch_to_srgb),
+        ];
+
+        for (to, from) in conversions {
+            for c in colors {
+                let got = from(to(c));
+                assert!(
+                    fabsf(got.r - c.r) < 1e-3
+                        && fabsf(got.g - c.g) < 1e-3
+                        && fabsf(got.b - c.b) < 1e-3,
+                    "{:?} != {:?}",
+                    got,
+                    c
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn achromatic_test() {
+        let gray = RGB::new(0.5, 0.5, 0.5);
+        assert!(srgb_to_hsl(gray)[0].is_nan());
+        assert!(srgb_to_hsv(gray)[0].is_nan());
+        assert!(srgb_to_lch(gray)[2].is_nan());
+        assert!(srgb_to_oklch(gray)[2].is_nan());
+        assert!(srgb_to_oklch(RGB::new(1.0, 1.0, 1.0))[2].is_nan());
+    }
+
+    #[test]
+    fn known_values_test() {
+        let [l, a, b] = srgb_to_oklab(RGB::new(1.0, 0.0, 0.0));
+        assert!(fabsf(l - 0.627_955) < 1e-3);
+        assert!(fabsf(a - 0.224_863) < 1e-3);
+        assert!(fabsf(b - 0.125_846) < 1e-3);
+
+        let [l, a, b] = srgb_to_lab(RGB::new(1.0, 0.0, 0.0));
+        assert!(fabsf(l - 54.29) < 0.1);
+        assert!(fabsf(a - 80.81) < 0.1);
+        assert!(fabsf(b - 69.89) < 0.1);
+
+        let [h, s, l] = srgb_to_hsl(RGB::new(0.2, 0.6, 0.9));
+        assert!(fabsf(h - 205.714) < 1e-2);
+        assert!(fabsf(s - 0.777_8) < 1e-3);
+        assert!(fabsf(l - 0.55) < 1e-3);
+    }
+}
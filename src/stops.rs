@@ -0,0 +1,932 @@
+//! A module contains [Stops] a multi-stop gradient with positioned colors.
+
+use core::marker::PhantomData;
+
+use libm::sqrtf;
+
+use crate::{
+    gradient::normalize_rgb,
+    interpolation::{ColorSpace, Easing, Float, HueInterpolation, Interpolation, Spline},
+    rgba::composite,
+    space, Component, RGB, RGBA,
+};
+
+/// A color placed on a gradient line.
+///
+/// The offset is a position on a line in [0, 1] range.
+/// If it's not set the stop is evenly distributed between its neighbors,
+/// the same way it's done in CSS.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct ColorStop {
+    /// Color
+    pub color: RGB,
+    /// Opacity
+    pub alpha: u8,
+    /// Position
+    pub offset: Option<f32>,
+}
+
+impl ColorStop {
+    /// Creates a new opaque [ColorStop] at a given position.
+    pub const fn new(color: RGB, offset: f32) -> Self {
+        Self {
+            color,
+            alpha: u8::MAX,
+            offset: Some(offset),
+        }
+    }
+
+    /// Sets an opacity of the stop.
+    pub const fn alpha(mut self, alpha: u8) -> Self {
+        self.alpha = alpha;
+        self
+    }
+}
+
+impl Default for ColorStop {
+    fn default() -> Self {
+        Self::from(RGB::default())
+    }
+}
+
+impl From<RGB> for ColorStop {
+    fn from(color: RGB) -> Self {
+        Self {
+            color,
+            alpha: u8::MAX,
+            offset: None,
+        }
+    }
+}
+
+impl From<RGBA> for ColorStop {
+    fn from(color: RGBA) -> Self {
+        Self::from(color.rgb()).alpha(color.a)
+    }
+}
+
+impl From<&RGBA> for ColorStop {
+    fn from(color: &RGBA) -> Self {
+        Self::from(*color)
+    }
+}
+
+impl From<&RGB> for ColorStop {
+    fn from(color: &RGB) -> Self {
+        Self::from(*color)
+    }
+}
+
+impl From<&ColorStop> for ColorStop {
+    fn from(stop: &ColorStop) -> Self {
+        *stop
+    }
+}
+
+impl From<(RGB, f32)> for ColorStop {
+    fn from((color, offset): (RGB, f32)) -> Self {
+        Self::new(color, offset)
+    }
+}
+
+impl From<(RGBA, f32)> for ColorStop {
+    fn from((color, offset): (RGBA, f32)) -> Self {
+        Self::new(color.rgb(), offset).alpha(color.a)
+    }
+}
+
+/// A gradient which consists of a list of color stops.
+///
+/// Stops without an offset are evenly distributed.
+/// Offsets are clamped to [0, 1] range and
+/// an offset which is less then a previous one is moved up to it.
+///
+/// ```
+/// use tiny_gradient::{stops::{ColorStop, Stops}, RGB};
+///
+/// let stops = Stops::new([
+///     ColorStop::new(RGB::new(255, 0, 0), 0.0),
+///     ColorStop::new(RGB::new(0, 0, 255), 0.3),
+///     ColorStop::new(RGB::new(0, 255, 0), 1.0),
+/// ]);
+///
+/// for color in stops.colors(10) {
+///     println!("{:?}", color);
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Stops<I> {
+    stops: I,
+    pub(crate) interpolation: Interpolation,
+    pub(crate) easing: Easing,
+    pub(crate) bands: Option<Bands>,
+    pub(crate) backdrop: Option<RGB>,
+    pub(crate) uniform: bool,
+}
+
+impl<I> Stops<I> {
+    /// Creates a new [Stops] from a list of colors or [ColorStop]s.
+    pub const fn new(stops: I) -> Self {
+        Self {
+            stops,
+            interpolation: Interpolation::new(),
+            easing: Easing::Linear,
+            bands: None,
+            backdrop: None,
+            uniform: false,
+        }
+    }
+
+    /// Resamples the gradient so equal steps give equal perceptual change.
+    ///
+    /// A change is measured as a distance in OKLab color space.
+    ///
+    /// The gradient is measured at 65 points on each [Stops::at] call
+    /// and each time [Stops::colors] is created,
+    /// so a single iterator is a lot cheaper than many `at` calls.
+    ///
+    /// ```
+    /// use tiny_gradient::Gradient;
+    ///
+    /// let stops = Gradient::Forest.stops().uniform();
+    ///
+    /// for color in stops.colors(10) {
+    ///     println!("{:?}", color);
+    /// }
+    /// ```
+    pub const fn uniform(mut self) -> Self {
+        self.uniform = true;
+        self
+    }
+
+    /// Sets a color which translucent colors are placed over.
+    ///
+    /// By default alpha is ignored and colors are blended as if they were opaque,
+    /// only [Stops::at_rgba] takes it into account.
+    ///
+    /// ```
+    /// use tiny_gradient::{stops::Stops, RGB, RGBA};
+    ///
+    /// let stops = Stops::new([RGBA::new(255, 0, 0, 255), RGBA::new(255, 0, 0, 0)])
+    ///     .backdrop(RGB::new(0, 0, 255));
+    ///
+    /// assert_eq!(stops.at(1.0), Some(RGB::new(0, 0, 255)));
+    /// ```
+    pub const fn backdrop(mut self, backdrop: RGB) -> Self {
+        self.backdrop = Some(backdrop);
+        self
+    }
+
+    /// Sets a color space in which colors are interpolated.
+    ///
+    /// Default is [ColorSpace::Corrected].
+    pub const fn space(mut self, space: ColorSpace) -> Self {
+        self.interpolation.space = space;
+        self
+    }
+
+    /// Sets a direction of hue interpolation.
+    ///
+    /// Default is [HueInterpolation::Shorter].
+    pub const fn hue(mut self, hue: HueInterpolation) -> Self {
+        self.interpolation.hue = hue;
+        self
+    }
+
+    /// Sets a curve which joins stops together.
+    ///
+    /// Default is [Spline::Linear].
+    pub const fn spline(mut self, spline: Spline) -> Self {
+        self.interpolation.spline = spline;
+        self
+    }
+
+    /// Sets all interpolation settings at once.
+    ///
+    /// An easing of [Interpolation] is applied to each pair of stops.
+    pub const fn interpolation(mut self, interpolation: Interpolation) -> Self {
+        self.interpolation = interpolation;
+        self
+    }
+
+    /// Sets an easing of the whole gradient.
+    ///
+    /// Default is [Easing::Linear].
+    pub const fn easing(mut self, easing: Easing) -> Self {
+        self.easing = easing;
+        self
+    }
+
+    /// Sets an easing applied to each pair of stops separately.
+    ///
+    /// Default is [Easing::Linear].
+    pub const fn segment_easing(mut self, easing: Easing) -> Self {
+        self.interpolation.easing = easing;
+        self
+    }
+
+    /// Splits the gradient into `classes` equal bands of solid colors.
+    ///
+    /// The first band has the first color and the last band has the last one.
+    /// `smoothness` is a fraction of a band width in [0, 1] range
+    /// over which neighbor bands are blended together.
+    ///
+    /// Zero `classes` turns it off.
+    ///
+    /// ```
+    /// use tiny_gradient::{stops::Stops, RGB};
+    ///
+    /// let red = RGB::new(255, 0, 0);
+    /// let blue = RGB::new(0, 0, 255);
+    ///
+    /// let stops = Stops::new([red, blue]).sharp(2, 0.0);
+    ///
+    /// assert_eq!(stops.at(0.4), Some(red));
+    /// assert_eq!(stops.at(0.6), Some(blue));
+    /// ```
+    pub const fn sharp(mut self, classes: usize, smoothness: f32) -> Self {
+        self.bands = Bands::new(classes, smoothness);
+        self
+    }
+}
+
+impl<I> Stops<I>
+where
+    I: IntoIterator + Clone,
+    I::IntoIter: Clone,
+    I::Item: Into<ColorStop>,
+{
+    /// Returns a color at a given position in [0, 1] range.
+    ///
+    /// It returns [None] in case there's no stops.
+    ///
+    /// ```
+    /// use tiny_gradient::{stops::Stops, RGB};
+    ///
+    /// let stops = Stops::new([RGB::new(0, 0, 0), RGB::new(255, 0, 0), RGB::new(255, 255, 255)]);
+    ///
+    /// assert_eq!(stops.at(0.5), Some(RGB::new(255, 0, 0)));
+    /// ```
+    pub fn at(&self, t: f32) -> Option<RGB> {
+        self.at_as(t)
+    }
+
+    /// Returns a color at a given position in [0, 1] range
+    /// with a given component precision.
+    ///
+    /// ```
+    /// use tiny_gradient::{stops::Stops, RGB};
+    ///
+    /// let stops = Stops::new([RGB::new(0, 0, 0), RGB::new(255, 255, 255)]);
+    ///
+    /// assert_eq!(stops.at_as::<u16>(0.5), Some(RGB::new(31716, 31716, 31716)));
+    /// ```
+    pub fn at_as<T: Component>(&self, t: f32) -> Option<RGB<T>> {
+        let c = self.cursor(self.backdrop.is_none()).sample(t)?;
+        let c = flatten(c, self.backdrop);
+        Some(self.interpolation.quantize(c))
+    }
+
+    /// Returns a color along with its alpha at a given position in [0, 1] range.
+    ///
+    /// Colors are weighted by alpha, so a transparent stop doesn't tint its neighbors.
+    ///
+    /// ```
+    /// use tiny_gradient::{stops::Stops, RGBA};
+    ///
+    /// let stops = Stops::new([RGBA::new(255, 0, 0, 255), RGBA::new(255, 0, 0, 0)]);
+    ///
+    /// assert_eq!(stops.at_rgba(0.5), Some(RGBA::new(255, 0, 0, 127)));
+    /// ```
+    pub fn at_rgba(&self, t: f32) -> Option<RGBA> {
+        let c = self.cursor(false).sample(t)?;
+        Some(self.interpolation.quantize_alpha(c))
+    }
+
+    /// Returns an iterator over `n` evenly spaced colors of the gradient.
+    ///
+    /// The first color is taken at the beginning of a line and the last at the end.
+    pub fn colors(&self, n: usize) -> Colors<I::IntoIter> {
+        self.colors_as(n)
+    }
+
+    /// Returns an iterator over `n` evenly spaced colors of the gradient
+    /// with a given component precision.
+    pub fn colors_as<T: Component>(&self, n: usize) -> Colors<I::IntoIter, T> {
+        Colors {
+            cursor: self.cursor(self.backdrop.is_none()),
+            backdrop: self.backdrop,
+            i: 0,
+            n,
+            component: PhantomData,
+        }
+    }
+
+    // Alpha is dropped from stops of an opaque cursor.
+    fn cursor(&self, opaque: bool) -> Cursor<I::IntoIter> {
+        Cursor::new(
+            self.stops.clone().into_iter(),
+            self.interpolation,
+            self.easing,
+            self.bands,
+            self.uniform,
+            opaque,
+        )
+    }
+}
+
+/// An iterator over evenly spaced colors of [Stops].
+#[derive(Debug, Clone)]
+pub struct Colors<I, T = u8> {
+    cursor: Cursor<I>,
+    backdrop: Option<RGB>,
+    i: usize,
+    n: usize,
+    component: PhantomData<T>,
+}
+
+impl<I, T> Iterator for Colors<I, T>
+where
+    I: Iterator + Clone,
+    I::Item: Into<ColorStop>,
+    T: Component,
+{
+    type Item = RGB<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.i == self.n {
+            return None;
+        }
+
+        let t = self.i as f32 / (self.n - 1) as f32;
+
+        let color = self.cursor.sample(t)?;
+
+        self.i += 1;
+
+        let color = flatten(color, self.backdrop);
+
+        Some(self.cursor.interpolation.quantize(color))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self.cursor.to {
+            Some(_) => (self.n - self.i, Some(self.n - self.i)),
+            None => (0, Some(0)),
+        }
+    }
+}
+
+impl<I, T> ExactSizeIterator for Colors<I, T>
+where
+    I: Iterator + Clone,
+    I::Item: Into<ColorStop>,
+    T: Component,
+{
+}
+
+// Drops alpha of a color placing it over a backdrop if there's one.
+fn flatten(c: RGBA<f32>, backdrop: Option<RGB>) -> RGB<f32> {
+    match backdrop {
+        Some(backdrop) => composite(c, normalize_rgb(backdrop)),
+        None => c.rgb(),
+    }
+}
+
+/// Equal bands of solid colors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) struct Bands {
+    classes: usize,
+    smoothness: Float,
+}
+
+impl Bands {
+    pub(crate) const fn new(classes: usize, smoothness: f32) -> Option<Self> {
+        match classes {
+            0 => None,
+            classes => Some(Self {
+                classes,
+                smoothness: Float(smoothness.clamp(0.0, 1.0)),
+            }),
+        }
+    }
+}
+
+const LENGTH_SAMPLES: usize = 64;
+
+/// A perceptual length of a line passed at evenly spaced positions,
+/// it's normalized to [0, 1] range.
+#[derive(Debug, Clone, Copy)]
+struct Lengths([f32; LENGTH_SAMPLES + 1]);
+
+impl Lengths {
+    // Returns a position at which a given fraction of the length is passed.
+    fn position(&self, mut t: f32) -> f32 {
+        if t.is_nan() {
+            t = 0.0;
+        }
+
+        let t = t.clamp(0.0, 1.0);
+        let i = self.0.partition_point(|&length| length < t);
+        if i == 0 {
+            return 0.0;
+        }
+
+        let (l1, l2) = (self.0[i - 1], self.0[i]);
+        let frac = (t - l1) / (l2 - l1);
+
+        ((i - 1) as f32 + frac) / LENGTH_SAMPLES as f32
+    }
+}
+
+/// A segment of stops which moves along a line.
+#[derive(Debug, Clone)]
+struct Cursor<I> {
+    start: Positions<I>,
+    positions: Positions<I>,
+    interpolation: Interpolation,
+    easing: Easing,
+    bands: Option<Bands>,
+    lengths: Option<Lengths>,
+    opaque: bool,
+    prev: Option<(RGBA<f32>, f32)>,
+    from: Option<(RGBA<f32>, f32)>,
+    to: Option<(RGBA<f32>, f32)>,
+}
+
+impl<I> Cursor<I>
+where
+    I: Iterator + Clone,
+    I::Item: Into<ColorStop>,
+{
+    fn new(
+        stops: I,
+        interpolation: Interpolation,
+        easing: Easing,
+        bands: Option<Bands>,
+        uniform: bool,
+        opaque: bool,
+    ) -> Self {
+        let start = Positions::new(stops);
+        let mut positions = start.clone();
+        let first = positions.next().map(|stop| point(stop, opaque));
+
+        let mut cursor = Self {
+            start,
+            positions,
+            interpolation,
+            easing,
+            bands,
+            lengths: None,
+            opaque,
+            prev: None,
+            from: None,
+            to: first,
+        };
+
+        if uniform {
+            cursor.lengths = cursor.measure();
+        }
+
+        cursor
+    }
+
+    // Measures a perceptual length of the line as a distance in OKLab.
+    fn measure(&mut self) -> Option<Lengths> {
+        let mut lengths = [0.0; LENGTH_SAMPLES + 1];
+
+        let mut prev = space::srgb_to_oklab(self.seek(0.0)?.rgb());
+        for i in 1..=LENGTH_SAMPLES {
+            let c = self.seek(i as f32 / LENGTH_SAMPLES as f32)?;
+            let c = space::srgb_to_oklab(c.rgb());
+
+            let [dl, da, db] = [c[0] - prev[0], c[1] - prev[1], c[2] - prev[2]];
+            lengths[i] = lengths[i - 1] + sqrtf(dl * dl + da * da + db * db);
+            prev = c;
+        }
+
+        let total = lengths[LENGTH_SAMPLES];
+        if total.is_nan() || total == 0.0 {
+            return None;
+        }
+
+        for length in &mut lengths {
+            *length /= total;
+        }
+
+        Some(Lengths(lengths))
+    }
+
+    // Finds a color at a given position of the line.
+    fn locate(&mut self, t: f32) -> Option<RGBA<f32>> {
+        match self.lengths {
+            Some(lengths) => self.seek(lengths.position(t)),
+            None => self.seek(t),
+        }
+    }
+
+    // Returns a color at a given position taking into account all the settings.
+    //
+    // The color is normalized to 0..1.
+    fn sample(&mut self, t: f32) -> Option<RGBA<f32>> {
+        let t = self.easing.apply(t);
+
+        let bands = match self.bands {
+            Some(bands) => bands,
+            None => return self.locate(t),
+        };
+
+        let n = bands.classes;
+        let class_color = |cursor: &mut Self, k: usize| {
+            let t = if n == 1 {
+                0.0
+            } else {
+                k as f32 / (n - 1) as f32
+            };
+            cursor.locate(t)
+        };
+
+        let x = t * n as f32;
+        let k = (x as usize).min(n - 1);
+        let frac = x - k as f32;
+        let half = bands.smoothness.0 / 2.0;
+
+        // blend a band edge with a neighbor band
+        let (k1, k2, mix) = if k > 0 && frac < half {
+            (k - 1, k, (frac + half) / (2.0 * half))
+        } else if k + 1 < n && frac > 1.0 - half {
+            (k, k + 1, (frac - 1.0 + half) / (2.0 * half))
+        } else {
+            return class_color(self, k);
+        };
+
+        let c1 = class_color(self, k1)?;
+        let c2 = class_color(self, k2)?;
+
+        let interpolation = Interpolation {
+            easing: Easing::Linear,
+            ..self.interpolation
+        };
+
+        Some(interpolation.mix_alpha(c1, c2, mix))
+    }
+
+    // It's cheap to move forward, moving back starts from the beginning.
+    fn seek(&mut self, mut t: f32) -> Option<RGBA<f32>> {
+        if t.is_nan() {
+            t = 0.0;
+        }
+
+        let opaque = self.opaque;
+        if matches!(self.from, Some(from) if t < from.1) {
+            self.positions = self.start.clone();
+            self.prev = None;
+            self.from = None;
+            self.to = self.positions.next().map(|stop| point(stop, opaque));
+        }
+
+        let mut to = self.to?;
+        while t >= to.1 {
+            match self.positions.next() {
+                Some(stop) => {
+                    self.prev = self.from;
+                    self.from = Some(to);
+                    to = point(stop, opaque);
+                }
+                None => break,
+            }
+        }
+
+        self.to = Some(to);
+
+        let from = match self.from {
+            Some(from) => from,
+            None => return Some(to.0),
+        };
+
+        // B-spline doesn't go through stops
+        let hit = t == from.1 && self.interpolation.spline != Spline::BSpline;
+
+        let color = if hit {
+            from.0
+        } else if t >= to.1 {
+            to.0
+        } else {
+            let mix = (t - from.1) / (to.1 - from.1);
+
+            let next = match self.interpolation.spline {
+                Spline::Linear => None,
+                _ => self
+                    .positions
+                    .clone()
+                    .next()
+                    .map(|stop| point(stop, opaque)),
+            };
+
+            self.interpolation
+                .mix_spline(self.prev, from, to, next, mix)
+        };
+
+        Some(color)
+    }
+}
+
+// Converts a stop with a resolved offset into a normalized color.
+fn point(stop: ColorStop, opaque: bool) -> (RGBA<f32>, f32) {
+    let alpha = if opaque {
+        1.0
+    } else {
+        stop.alpha.to_normalized()
+    };
+    let c = normalize_rgb(stop.color);
+    let c = RGBA::new(c.r, c.g, c.b, alpha);
+
+    (c, stop.offset.unwrap_or_default())
+}
+
+/// An iterator which resolves offsets of color stops.
+#[derive(Debug, Clone)]
+pub(crate) struct Positions<I> {
+    stops: I,
+    first: bool,
+    last: f32,
+    step: f32,
+    run: usize,
+}
+
+impl<I> Positions<I> {
+    pub(crate) fn new(stops: I) -> Self {
+        Self {
+            stops,
+            first: true,
+            last: 0.0,
+            step: 0.0,
+            run: 0,
+        }
+    }
+}
+
+impl<I> Iterator for Positions<I>
+where
+    I: Iterator + Clone,
+    I::Item: Into<ColorStop>,
+{
+    type Item = ColorStop;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut stop: ColorStop = self.stops.next()?.into();
+
+        let offset = match stop.offset {
+            Some(offset) => {
+                self.run = 0;
+                offset.clamp(0.0, 1.0).max(self.last)
+            }
+            None if self.first => 0.0,
+            None => {
+                if self.run == 0 {
+                    self.start_run();
+                }
+
+                self.run -= 1;
+                self.last + self.step
+            }
+        };
+
+        self.first = false;
+        self.last = offset;
+
+        stop.offset = Some(offset);
+
+        Some(stop)
+    }
+}
+
+impl<I> Positions<I>
+where
+    I: Iterator + Clone,
+    I::Item: Into<ColorStop>,
+{
+    // Look up a next positioned stop, to spread the stops in between evenly.
+    fn start_run(&mut self) {
+        let mut rest = self.stops.clone();
+        let mut run = 1;
+        let end = loop {
+            match rest.next().map(Into::into) {
+                Some(ColorStop {
+                    offset: Some(offset),
+                    ..
+                }) => break Some(offset),
+                Some(_) => run += 1,
+                None => break None,
+            }
+        };
+
+        let (end, parts) = match end {
+            Some(offset) => (offset.clamp(0.0, 1.0).max(self.last), run + 1),
+            None => (1.0, run),
+        };
+
+        self.run = run;
+        self.step = (end - self.last) / parts as f32;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{space, ColorSpace, ColorStop, Easing, Positions, Spline, Stops, RGB, RGBA};
+
+    const RED: RGB = RGB::new(255, 0, 0);
+    const GREEN: RGB = RGB::new(0, 255, 0);
+    const BLUE: RGB = RGB::new(0, 0, 255);
+
+    #[test]
+    fn positions_test() {
+        test_positions(&[RED, GREEN, BLUE].map(ColorStop::from), &[0.0, 0.5, 1.0]);
+        test_positions(&[ColorStop::from(RED)], &[0.0]);
+        test_positions(
+            &[
+                ColorStop::from(RED),
+                ColorStop::from(GREEN),
+                ColorStop::from(BLUE),
+                ColorStop::new(RED, 0.6),
+                ColorStop::from(GREEN),
+            ],
+            &[0.0, 0.2, 0.4, 0.6, 1.0],
+        );
+        test_positions(
+            &[
+                ColorStop::new(RED, 0.5),
+                ColorStop::new(GREEN, 0.2),
+                ColorStop::new(BLUE, 2.0),
+            ],
+            &[0.5, 0.5, 1.0],
+        );
+    }
+
+    #[test]
+    fn at_test() {
+        let stops = Stops::new([RED, GREEN, BLUE]);
+        assert_eq!(stops.at(0.0), Some(RED));
+        assert_eq!(stops.at(0.5), Some(GREEN));
+        assert_eq!(stops.at(1.0), Some(BLUE));
+        assert_eq!(stops.at(-1.0), Some(RED));
+        assert_eq!(stops.at(2.0), Some(BLUE));
+        assert_eq!(stops.at(0.25), Stops::new([RED, GREEN]).at(0.5));
+
+        let stops = Stops::new([ColorStop::new(RED, 0.2), ColorStop::new(BLUE, 0.4)]);
+        assert_eq!(stops.at(0.1), Some(RED));
+        assert_eq!(stops.at(0.5), Some(BLUE));
+
+        assert_eq!(Stops::new([] as [RGB; 0]).at(0.5), None);
+    }
+
+    #[test]
+    fn easing_test() {
+        let stops = Stops::new([RED, GREEN, BLUE]).easing(Easing::PowerIn(2.0));
+        assert_eq!(stops.at(0.5), Stops::new([RED, GREEN, BLUE]).at(0.25));
+        test_colors(
+            stops.colors(3),
+            &[RED, Stops::new([RED, GREEN]).at(0.5).unwrap(), BLUE],
+        );
+
+        let stops = Stops::new([RED, GREEN, BLUE]).segment_easing(Easing::PowerIn(2.0));
+        assert_eq!(stops.at(0.5), Some(GREEN));
+        assert_eq!(stops.at(0.75), Stops::new([GREEN, BLUE]).at(0.25));
+
+        let stops = Stops::new([RED, GREEN, BLUE]).easing(Easing::CubicBezier(0.5, 2.0, 0.5, -1.0));
+        let mut colors = stops.colors(20);
+        assert_eq!(colors.next(), Some(RED));
+        assert_eq!(colors.last(), Some(BLUE));
+    }
+
+    #[test]
+    fn spline_test() {
+        let stops = Stops::new([RED, GREEN, BLUE, RED]).spline(Spline::CatmullRom);
+        test_colors(stops.colors(7).step_by(2), &[RED, GREEN, BLUE, RED]);
+        assert_ne!(stops.at(0.5), Stops::new([RED, GREEN, BLUE, RED]).at(0.5));
+
+        let stops = Stops::new([RED, GREEN, BLUE, RED]).spline(Spline::BSpline);
+        assert_eq!(stops.at(0.0), Some(RED));
+        assert_ne!(stops.at(1.0 / 3.0), Some(GREEN));
+        assert_eq!(stops.at(1.0), Some(RED));
+    }
+
+    #[test]
+    fn sharp_test() {
+        let stops = Stops::new([RED, GREEN, BLUE]).sharp(3, 0.0);
+        test_colors(stops.colors(6), &[RED, RED, GREEN, GREEN, BLUE, BLUE]);
+        assert_eq!(stops.at(1.0), Some(BLUE));
+
+        let stops = Stops::new([RED, BLUE]).sharp(3, 0.0);
+        assert_eq!(stops.at(0.5), Stops::new([RED, BLUE]).at(0.5));
+
+        let stops = Stops::new([RED, BLUE]).sharp(2, 0.5);
+        assert_eq!(stops.at(0.3), Some(RED));
+        assert_eq!(stops.at(0.5), Stops::new([RED, BLUE]).at(0.5));
+        assert_eq!(stops.at(0.7), Some(BLUE));
+        assert_ne!(stops.at(0.45), Some(RED));
+
+        let stops = Stops::new([RED, BLUE]).sharp(1, 0.0);
+        test_colors(stops.colors(3), &[RED, RED, RED]);
+    }
+
+    #[test]
+    fn colors_test() {
+        let stops = Stops::new([RED, BLUE]);
+        test_colors(stops.colors(0), &[]);
+        test_colors(stops.colors(1), &[RED]);
+        test_colors(stops.colors(2), &[RED, BLUE]);
+
+        let stops = Stops::new([RED, GREEN, BLUE]);
+        let mut colors = stops.colors(5);
+        assert_eq!(colors.len(), 5);
+        assert_eq!(colors.next(), Some(RED));
+        assert_eq!(colors.nth(1), Some(GREEN));
+        assert_eq!(colors.nth(1), Some(BLUE));
+
+        let stops = Stops::new([
+            ColorStop::new(RED, 0.0),
+            ColorStop::new(RED, 0.5),
+            ColorStop::new(BLUE, 0.5),
+            ColorStop::new(BLUE, 1.0),
+        ]);
+        test_colors(stops.colors(4), &[RED, RED, BLUE, BLUE]);
+
+        let stops = Stops::new([] as [RGB; 0]);
+        test_colors(stops.colors(10), &[]);
+    }
+
+    #[test]
+    fn uniform_test() {
+        let black = RGB::new(0, 0, 0);
+        let white = RGB::new(255, 255, 255);
+
+        let stops = Stops::new([
+            ColorStop::new(black, 0.0),
+            ColorStop::new(white, 0.2),
+            ColorStop::new(white, 1.0),
+        ]);
+        assert_eq!(stops.at(0.5), Some(white));
+
+        let stops = stops.uniform();
+        assert_eq!(stops.at(0.0), Some(black));
+        assert_eq!(stops.at(1.0), Some(white));
+
+        // lightness of OKLab changes evenly
+        let mut colors = stops.colors_as::<f32>(5);
+        let mut prev = space::srgb_to_oklab(colors.next().unwrap())[0];
+        for c in colors {
+            let l = space::srgb_to_oklab(c)[0];
+            assert!((l - prev - 0.25).abs() < 0.01, "{} {}", prev, l);
+            prev = l;
+        }
+
+        let stops = Stops::new([RED, RED]).uniform();
+        test_colors(stops.colors(3), &[RED, RED, RED]);
+    }
+
+    #[test]
+    fn alpha_test() {
+        let stops = Stops::new([ColorStop::new(RED, 0.0), ColorStop::new(BLUE, 1.0).alpha(0)]);
+        assert_eq!(stops.at_rgba(0.5), Some(RGBA::new(255, 0, 0, 127)));
+        assert_eq!(stops.at_rgba(1.0), Some(RGBA::new(0, 0, 255, 0)));
+        assert_eq!(stops.at(0.5), Stops::new([RED, BLUE]).at(0.5));
+        assert_eq!(stops.at(1.0), Some(BLUE));
+
+        let stops = stops.space(ColorSpace::Srgb).backdrop(GREEN);
+        test_colors(stops.colors(3), &[RED, RGB::new(127, 127, 0), GREEN]);
+
+        let stops = Stops::new([RGBA::new(0, 0, 255, 128)]).backdrop(RED);
+        assert_eq!(stops.at(0.5), Some(RGB::new(127, 0, 128)));
+    }
+
+    #[test]
+    fn precision_test() {
+        let stops = Stops::new([RED, BLUE]).space(ColorSpace::Srgb);
+        assert_eq!(stops.at_as::<u16>(0.0), Some(RGB::new(65535, 0, 0)));
+        assert_eq!(stops.at_as::<u16>(0.5), Some(RGB::new(32767, 0, 32767)));
+        assert_eq!(stops.at_as::<f32>(0.5), Some(RGB::new(0.5, 0.0, 0.5)));
+
+        let colors = [RGB::new(1.0, 0.0, 0.0), RGB::new(0.0, 0.0, 1.0)];
+        assert!(stops.colors_as::<f32>(2).eq(colors));
+    }
+
+    fn test_colors(mut iter: impl Iterator<Item = RGB>, expected: &[RGB]) {
+        for rgb in expected {
+            let got = iter.next().unwrap();
+            assert_eq!(got, *rgb);
+        }
+
+        assert!(iter.next().is_none());
+    }
+
+    fn test_positions(stops: &[ColorStop], expected: &[f32]) {
+        let mut positions = Positions::new(stops.iter());
+        for offset in expected {
+            let got = positions.next().unwrap().offset.unwrap();
+            assert!((got - offset).abs() < 1e-6, "{} != {}", got, offset);
+        }
+
+        assert!(positions.next().is_none());
+    }
+}